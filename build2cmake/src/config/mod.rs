@@ -3,7 +3,10 @@ use serde::Deserialize;
 pub mod v1;
 
 mod v2;
-pub use v2::{Build, ComputeFramework, Dependencies, Kernel, Torch};
+pub use v2::{Build, BuildSettings, ComputeFramework, Dependencies, Kernel, Profile, Torch};
+
+mod version_req;
+pub use version_req::{Version, VersionReq};
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -1,20 +1,57 @@
 use std::{collections::HashMap, path::PathBuf};
 
+use clap::ValueEnum;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use super::v1;
+use super::version_req::VersionReq;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Build {
     pub general: General,
+    #[serde(default)]
+    pub build: BuildSettings,
     pub torch: Option<Torch>,
 
     #[serde(rename = "kernel", default)]
     pub kernels: HashMap<String, Kernel>,
 }
 
+/// The `[build]` table: knobs that affect how the generated CMake/setup.py is
+/// compiled rather than what it compiles.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct BuildSettings {
+    #[serde(default)]
+    pub profile: Profile,
+}
+
+/// Mirrors the `DEBUG`/`REL_WITH_DEB_INFO` build toggles PyTorch's own `setup.py`
+/// exposes, so kernel authors can get an unoptimized or symbol-carrying build
+/// without hand-editing the generated CMake.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[clap(rename_all = "kebab-case")]
+pub enum Profile {
+    #[default]
+    Release,
+    Debug,
+    RelWithDebInfo,
+}
+
+impl Profile {
+    /// The `CMAKE_BUILD_TYPE` this profile maps to.
+    pub fn cmake_build_type(&self) -> &'static str {
+        match self {
+            Profile::Release => "Release",
+            Profile::Debug => "Debug",
+            Profile::RelWithDebInfo => "RelWithDebInfo",
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename_all = "kebab-case")]
 pub struct General {
@@ -72,21 +109,23 @@ pub struct Kernel {
     pub depends: Vec<Dependencies>,
     pub include: Option<Vec<String>>,
     pub src: Vec<String>,
+
+    /// Source files (a subset of `src`) that keep debug info even in a `release`
+    /// build, mirroring PyTorch setup.py's per-file `USE_CUSTOM_DEBINFO`.
+    pub debug_sources: Option<Vec<String>>,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "lowercase")]
-pub enum Dependencies {
-    #[serde[rename = "cutlass_2_10"]]
-    Cutlass2_10,
-    #[serde[rename = "cutlass_3_5"]]
-    Cutlass3_5,
-    #[serde[rename = "cutlass_3_6"]]
-    Cutlass3_6,
-    #[serde[rename = "cutlass_3_8"]]
-    Cutlass3_8,
-    Torch,
+/// An open-ended dependency requirement, e.g. `{ name = "cutlass", req = ">=3.5, <4" }`.
+/// Replaces the old closed enum of hardcoded point releases (`Cutlass2_10`,
+/// `Cutlass3_5`, ...) so that new CUTLASS/Torch releases don't require a code
+/// change, and old `build.toml` files don't break as new versions ship. `req` is
+/// parsed and can be tested with `VersionReq::matches`, but the build generators
+/// do not select a concrete dependency version from it.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dependencies {
+    pub name: String,
+    pub req: VersionReq,
 }
 
 impl From<v1::Build> for Build {
@@ -99,6 +138,8 @@ impl From<v1::Build> for Build {
         Self {
             general: General::from(build.general, universal),
             torch: build.torch.map(Into::into),
+            // v1 build.toml predates the `[build]` table; fall back to `release`.
+            build: BuildSettings::default(),
             kernels: build
                 .kernels
                 .into_iter()
@@ -131,6 +172,8 @@ impl From<v1::Kernel> for Kernel {
             depends: kernel.depends,
             include: kernel.include,
             src: kernel.src,
+            // v1 build.toml has no notion of per-file debug info.
+            debug_sources: None,
         }
     }
 }
@@ -0,0 +1,241 @@
+//! A small, self-contained semver-style version requirement matcher, used so that
+//! `Dependencies` entries in `build.toml` can express things like `>=3.5, <4` or
+//! `^3.8` instead of being pinned to a hardcoded enum variant per release.
+
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl std::str::FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut parts = s.split('.');
+        let major = parts
+            .next()
+            .ok_or_else(|| format!("Version is missing a major component: {s}"))?
+            .parse()
+            .map_err(|_| format!("Invalid major version in {s}"))?;
+        let minor = parts
+            .next()
+            .map(|p| p.parse())
+            .transpose()
+            .map_err(|_| format!("Invalid minor version in {s}"))?
+            .unwrap_or(0);
+        let patch = parts
+            .next()
+            .map(|p| p.parse())
+            .transpose()
+            .map_err(|_| format!("Invalid patch version in {s}"))?
+            .unwrap_or(0);
+
+        Ok(Version {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Caret,
+    Tilde,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Comparator {
+    op: Op,
+    version: Version,
+}
+
+impl Comparator {
+    fn matches(&self, candidate: &Version) -> bool {
+        match self.op {
+            Op::Eq => candidate == &self.version,
+            Op::Gt => candidate > &self.version,
+            Op::Ge => candidate >= &self.version,
+            Op::Lt => candidate < &self.version,
+            Op::Le => candidate <= &self.version,
+            // `^1.2.3` means `>=1.2.3, <2.0.0`.
+            Op::Caret => {
+                let upper = Version {
+                    major: self.version.major + 1,
+                    minor: 0,
+                    patch: 0,
+                };
+                candidate >= &self.version && candidate < &upper
+            }
+            // `~1.2.3` means `>=1.2.3, <1.3.0`.
+            Op::Tilde => {
+                let upper = Version {
+                    major: self.version.major,
+                    minor: self.version.minor + 1,
+                    patch: 0,
+                };
+                candidate >= &self.version && candidate < &upper
+            }
+        }
+    }
+}
+
+fn parse_comparator(term: &str) -> Result<Comparator, String> {
+    let term = term.trim();
+    let (op, rest) = if let Some(rest) = term.strip_prefix(">=") {
+        (Op::Ge, rest)
+    } else if let Some(rest) = term.strip_prefix("<=") {
+        (Op::Le, rest)
+    } else if let Some(rest) = term.strip_prefix('>') {
+        (Op::Gt, rest)
+    } else if let Some(rest) = term.strip_prefix('<') {
+        (Op::Lt, rest)
+    } else if let Some(rest) = term.strip_prefix('=') {
+        (Op::Eq, rest)
+    } else if let Some(rest) = term.strip_prefix('^') {
+        (Op::Caret, rest)
+    } else if let Some(rest) = term.strip_prefix('~') {
+        (Op::Tilde, rest)
+    } else {
+        (Op::Caret, term)
+    };
+
+    let version = rest.trim().parse()?;
+    Ok(Comparator { op, version })
+}
+
+/// A comma-separated list of version comparators, e.g. `>=3.5, <4` or `^3.8`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VersionReq {
+    raw: String,
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    pub fn matches(&self, candidate: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(candidate))
+    }
+}
+
+impl std::str::FromStr for VersionReq {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let comparators = s
+            .split(',')
+            .map(parse_comparator)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(VersionReq {
+            raw: s.to_string(),
+            comparators,
+        })
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionReq {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for VersionReq {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn caret_allows_minor_and_patch_bumps_but_not_major() {
+        let req: VersionReq = "^3.8".parse().unwrap();
+
+        assert!(req.matches(&version("3.8.0")));
+        assert!(req.matches(&version("3.8.1")));
+        assert!(req.matches(&version("3.9.0")));
+        assert!(req.matches(&version("3.99.99")));
+
+        assert!(!req.matches(&version("3.7.99")));
+        assert!(!req.matches(&version("4.0.0")));
+    }
+
+    #[test]
+    fn tilde_allows_patch_bumps_but_not_minor() {
+        let req: VersionReq = "~3.8.2".parse().unwrap();
+
+        assert!(req.matches(&version("3.8.2")));
+        assert!(req.matches(&version("3.8.99")));
+
+        assert!(!req.matches(&version("3.8.1")));
+        assert!(!req.matches(&version("3.9.0")));
+    }
+
+    #[test]
+    fn explicit_range_matches_half_open_interval() {
+        let req: VersionReq = ">=3.5, <4".parse().unwrap();
+
+        assert!(req.matches(&version("3.5.0")));
+        assert!(req.matches(&version("3.99.99")));
+
+        assert!(!req.matches(&version("3.4.99")));
+        assert!(!req.matches(&version("4.0.0")));
+    }
+
+    #[test]
+    fn boundary_versions_are_inclusive_at_the_lower_bound_only() {
+        // Caret and tilde upper bounds are exclusive: the version that would
+        // naively look like "one past the end" must not match.
+        let caret: VersionReq = "^1.2.3".parse().unwrap();
+        assert!(caret.matches(&version("1.2.3")));
+        assert!(!caret.matches(&version("2.0.0")));
+
+        let tilde: VersionReq = "~1.2.3".parse().unwrap();
+        assert!(tilde.matches(&version("1.2.3")));
+        assert!(!tilde.matches(&version("1.3.0")));
+    }
+
+    #[test]
+    fn bare_version_defaults_to_caret() {
+        let req: VersionReq = "3.8".parse().unwrap();
+        assert!(req.matches(&version("3.8.5")));
+        assert!(!req.matches(&version("4.0.0")));
+    }
+}
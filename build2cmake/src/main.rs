@@ -0,0 +1,477 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::{Parser, Subcommand};
+use eyre::{bail, ensure, Context, Result};
+use minijinja::Environment;
+
+mod torch;
+use torch::write_torch_ext;
+
+mod config;
+use config::{Build, BuildCompat, ComputeFramework, Profile};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Generate CMake files for Torch extension builds.
+    GenerateTorch {
+        #[arg(name = "BUILD_TOML")]
+        build_toml: PathBuf,
+
+        #[arg(name = "TARGET_DIR")]
+        target_dir: Option<PathBuf>,
+
+        /// Don't write generated files. Instead regenerate them into a scratch
+        /// directory and diff against what's already on disk, exiting non-zero on
+        /// any mismatch so CI can catch a `build.toml` whose generated files
+        /// weren't refreshed.
+        #[arg(long)]
+        check: bool,
+
+        /// Override the `[build]` table's `profile` from the command line, e.g. to
+        /// get a debug build without editing `build.toml`.
+        #[arg(long)]
+        profile: Option<Profile>,
+    },
+
+    /// Validate the build.toml file.
+    Validate {
+        #[arg(name = "BUILD_TOML")]
+        build_toml: PathBuf,
+    },
+
+    /// Upgrade a v1 build.toml to the current v2 schema and write it back out.
+    Migrate {
+        #[arg(name = "BUILD_TOML")]
+        build_toml: PathBuf,
+
+        /// Print the migrated manifest instead of writing it to disk.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Write the migrated manifest to this path instead of overwriting
+        /// BUILD_TOML in place.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn main() -> Result<()> {
+    let args = Cli::parse();
+    match args.command {
+        Commands::GenerateTorch {
+            build_toml,
+            target_dir,
+            check,
+            profile,
+        } => generate_torch(build_toml, target_dir, check, profile),
+        Commands::Validate { build_toml } => validate(build_toml),
+        Commands::Migrate {
+            build_toml,
+            dry_run,
+            output,
+        } => migrate(build_toml, dry_run, output),
+    }
+}
+
+fn generate_torch(
+    build_toml: PathBuf,
+    target_dir: Option<PathBuf>,
+    check: bool,
+    profile: Option<Profile>,
+) -> Result<()> {
+    let target_dir = check_or_infer_target_dir(&build_toml, target_dir)?;
+
+    let mut build: Build = read_build_toml(&build_toml)?;
+    if let Some(profile) = profile {
+        build.build.profile = profile;
+    }
+
+    let mut env = Environment::new();
+    minijinja_embed::load_templates!(&mut env);
+
+    if check {
+        return check_generated_files(&env, &build, &target_dir);
+    }
+
+    let _file_set = write_torch_ext(&env, &build, target_dir)?;
+
+    Ok(())
+}
+
+/// Regenerate the Torch extension files into a scratch directory and diff them
+/// against what's already committed in `target_dir`, the same normalize-then-diff
+/// strategy trybuild uses for expected-output comparisons. Bails with a non-zero
+/// exit on any mismatch instead of writing anything to `target_dir`.
+fn check_generated_files(env: &Environment, build: &Build, target_dir: &Path) -> Result<()> {
+    let scratch_dir =
+        tempfile::tempdir().wrap_err("Cannot create scratch directory for --check")?;
+    let _file_set = write_torch_ext(env, build, scratch_dir.path().to_path_buf())?;
+
+    let mut mismatches = Vec::new();
+
+    for entry in std::fs::read_dir(scratch_dir.path())
+        .wrap_err("Cannot read scratch directory")?
+    {
+        let entry = entry.wrap_err("Cannot read scratch directory entry")?;
+        let generated_path = entry.path();
+        if !generated_path.is_file() {
+            continue;
+        }
+
+        let file_name = generated_path.file_name().expect("entry has a file name");
+        let committed_path = target_dir.join(file_name);
+
+        let generated = read_and_normalize(&generated_path, scratch_dir.path())?;
+        let committed = if committed_path.exists() {
+            read_and_normalize(&committed_path, scratch_dir.path())?
+        } else {
+            String::new()
+        };
+
+        if generated != committed {
+            mismatches.push((committed_path, unified_diff(&committed, &generated)));
+        }
+    }
+
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    for (path, diff) in &mismatches {
+        eprintln!("--- {} (committed)", path.to_string_lossy());
+        eprintln!("+++ {} (regenerated)", path.to_string_lossy());
+        eprint!("{diff}");
+    }
+
+    bail!(
+        "{} generated file(s) for `{}` are out of date; re-run `generate-torch` without --check",
+        mismatches.len(),
+        build.general.name
+    );
+}
+
+/// Read a generated or committed file and normalize it for comparison: CRLF to LF,
+/// trimmed, with the scratch directory's non-deterministic absolute path redacted
+/// so two runs against the same `build.toml` always compare equal.
+fn read_and_normalize(path: &Path, scratch_dir: &Path) -> Result<String> {
+    let mut content = String::new();
+    File::open(path)
+        .wrap_err_with(|| format!("Cannot open {} for reading", path.to_string_lossy()))?
+        .read_to_string(&mut content)
+        .wrap_err_with(|| format!("Cannot read from {}", path.to_string_lossy()))?;
+
+    Ok(content
+        .replace("\r\n", "\n")
+        .replace(&*scratch_dir.to_string_lossy(), "<GENERATED_DIR>")
+        .trim()
+        .to_string())
+}
+
+/// A minimal line-based unified diff. These generated files are small enough that
+/// showing every differing line is more useful than collapsing unchanged runs into
+/// hunks with context.
+fn unified_diff(committed: &str, generated: &str) -> String {
+    let committed_lines: Vec<&str> = committed.lines().collect();
+    let generated_lines: Vec<&str> = generated.lines().collect();
+    let common = longest_common_subsequence(&committed_lines, &generated_lines);
+
+    let mut out = String::new();
+    let (mut i, mut j, mut k) = (0, 0, 0);
+    while i < committed_lines.len() || j < generated_lines.len() {
+        if k < common.len()
+            && i < committed_lines.len()
+            && j < generated_lines.len()
+            && committed_lines[i] == common[k]
+            && generated_lines[j] == common[k]
+        {
+            out.push_str(&format!("  {}\n", committed_lines[i]));
+            i += 1;
+            j += 1;
+            k += 1;
+        } else if i < committed_lines.len() && (k >= common.len() || committed_lines[i] != common[k])
+        {
+            out.push_str(&format!("- {}\n", committed_lines[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+ {}\n", generated_lines[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+fn longest_common_subsequence<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<&'a str> {
+    let (n, m) = (a.len(), b.len());
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if a[i] == b[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            result.push(a[i]);
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+fn check_or_infer_target_dir(
+    build_toml: impl AsRef<Path>,
+    target_dir: Option<PathBuf>,
+) -> Result<PathBuf> {
+    let build_toml = build_toml.as_ref();
+    match target_dir {
+        Some(target_dir) => {
+            ensure!(
+                target_dir.is_dir(),
+                "`{}` is not a directory",
+                target_dir.to_string_lossy()
+            );
+            Ok(target_dir)
+        }
+        None => {
+            let absolute = std::path::absolute(build_toml)?;
+            match absolute.parent() {
+                Some(parent) => Ok(parent.to_owned()),
+                None => bail!(
+                    "Cannot get parent path of `{}`",
+                    build_toml.to_string_lossy()
+                ),
+            }
+        }
+    }
+}
+
+/// Validate a `build.toml` beyond the bare TOML-to-`Build` deserialization: walk the
+/// parsed configuration like a linter, checking that every referenced path actually
+/// exists, that no two kernels share a name once case is ignored (CMake target names
+/// are case-insensitive on some filesystems), and that declared dependencies make
+/// sense for the chosen backend. Errors make `validate` exit non-zero; warnings are
+/// printed but don't fail the check, mirroring `cargo check`'s error/warning split.
+fn validate(build_toml: PathBuf) -> Result<()> {
+    let build: Build = read_build_toml(&build_toml)?;
+    let build_dir = build_toml.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    check_paths_exist(&build, build_dir, &mut errors);
+    check_duplicate_kernel_names(&build, &mut warnings);
+    check_backend_support(&build, &mut warnings);
+    check_cutlass_sycl_version(&build, &mut errors);
+
+    for warning in &warnings {
+        eprintln!("warning: {warning}");
+    }
+    for error in &errors {
+        eprintln!("error: {error}");
+    }
+
+    println!(
+        "validate: {} kernel(s) checked, {} warning(s), {} error(s)",
+        build.kernels.len(),
+        warnings.len(),
+        errors.len()
+    );
+
+    ensure!(
+        errors.is_empty(),
+        "`{}` failed validation",
+        build_toml.to_string_lossy()
+    );
+
+    Ok(())
+}
+
+/// Confirm every `kernel.src`/`kernel.include` and `torch.src`/`torch.include` entry
+/// resolves to a file that actually exists, relative to the directory the
+/// `build.toml` lives in (the same base CMake resolves them against).
+fn check_paths_exist(build: &Build, build_dir: &Path, errors: &mut Vec<String>) {
+    let mut check = |label: &str, path: &str| {
+        if !build_dir.join(path).exists() {
+            errors.push(format!("{label} does not exist: `{path}`"));
+        }
+    };
+
+    if let Some(torch) = &build.torch {
+        for src in &torch.src {
+            check("torch.src entry", &src.to_string_lossy());
+        }
+        for include in torch.include.iter().flatten() {
+            check("torch.include entry", include);
+        }
+    }
+
+    for (name, kernel) in &build.kernels {
+        for src in &kernel.src {
+            check(&format!("kernel `{name}` src entry"), src);
+        }
+        for include in kernel.include.iter().flatten() {
+            check(&format!("kernel `{name}` include entry"), include);
+        }
+    }
+}
+
+/// Flag kernel names that only differ by case. The `[kernel.*]` tables already
+/// dedupe exact matches by virtue of being a TOML map, but two kernels named `Foo`
+/// and `foo` would still collide once they become CMake targets.
+fn check_duplicate_kernel_names(build: &Build, warnings: &mut Vec<String>) {
+    let mut seen: HashMap<String, &String> = HashMap::new();
+
+    for name in build.kernels.keys() {
+        let lowercase = name.to_lowercase();
+        if let Some(other) = seen.insert(lowercase, name) {
+            warnings.push(format!(
+                "kernel names `{other}` and `{name}` differ only in case"
+            ));
+        }
+    }
+}
+
+/// Mirror the "XPU backend doesn't need/support dependency" warning that
+/// `render_deps` emits at generation time: the Universal backend doesn't compile
+/// any kernel sources at all, so dependencies declared on a kernel are silently
+/// ignored rather than linked in.
+fn check_backend_support(build: &Build, warnings: &mut Vec<String>) {
+    if build.general.compute_framework != ComputeFramework::Universal {
+        return;
+    }
+
+    for (name, kernel) in &build.kernels {
+        for dep in &kernel.depends {
+            if dep.name != "torch" {
+                warnings.push(format!(
+                    "kernel `{name}` depends on `{}`, but the Universal backend doesn't compile kernel sources and will ignore it",
+                    dep.name
+                ));
+            }
+        }
+    }
+}
+
+/// If any kernel depends on `cutlass-sycl`, confirm `DPCPP_VERSION` maps to a known
+/// cutlass-sycl release, the same lookup `render_deps` performs when generating the
+/// XPU CMake build, so a typo'd or unsupported `DPCPP_VERSION` is caught by
+/// `validate` instead of failing generation later.
+fn check_cutlass_sycl_version(build: &Build, errors: &mut Vec<String>) {
+    let depends_on_cutlass_sycl = build
+        .kernels
+        .values()
+        .any(|kernel| kernel.depends.iter().any(|dep| dep.name == "cutlass-sycl"));
+
+    if !depends_on_cutlass_sycl {
+        return;
+    }
+
+    let dpcpp_version = env::var("DPCPP_VERSION").unwrap_or_else(|_| "2025.1".to_string());
+    if !matches!(dpcpp_version.as_str(), "2025.0" | "2025.1") {
+        errors.push(format!(
+            "no cutlass-sycl version mapped for DPCPP_VERSION {dpcpp_version}"
+        ));
+    }
+}
+
+// A cross-check of each kernel's `cuda-capabilities`/`rocm-archs` against the
+// architectures the build-variant matrix actually compiles for belongs here, but
+// isn't implemented: `kernel-compliance-check`'s `get_cuda_variants()`/
+// `get_rocm_variants()` return full variant strings like
+// `torch25-cxx11-cu121-x86_64-linux`, i.e. a CUDA/ROCm *toolkit* version per
+// variant, not a bare SM/gfx *architecture* code, and `build-variants.json`
+// carries no toolkit-version-to-architecture mapping to derive one from. A
+// hand-maintained guess at that mapping would validate against data `validate`
+// doesn't actually have, which is worse than not validating at all -- so this
+// check is left out of `validate` until that mapping exists for real.
+
+/// Read and parse a `build.toml`, accepting either the v1 or the current v2 schema.
+fn read_build_toml(build_toml: &Path) -> Result<Build> {
+    let mut toml_data = String::new();
+    File::open(build_toml)
+        .wrap_err_with(|| format!("Cannot open {} for reading", build_toml.to_string_lossy()))?
+        .read_to_string(&mut toml_data)
+        .wrap_err_with(|| format!("Cannot read from {}", build_toml.to_string_lossy()))?;
+
+    let compat: BuildCompat = toml::from_str(&toml_data)
+        .wrap_err_with(|| format!("Cannot parse TOML in {}", build_toml.to_string_lossy()))?;
+
+    Ok(compat.into())
+}
+
+/// Upgrade a v1 `build.toml` to the v2 schema and write the canonical manifest back
+/// to disk (or print it, with `--dry-run`), so downstream kernel repos can bulk
+/// upgrade their manifests instead of hand-editing them. With `--output`, the
+/// migrated manifest is written to a separate path instead, leaving `build_toml`
+/// untouched, e.g. to preview the upgrade alongside the original before committing
+/// to it.
+fn migrate(build_toml: PathBuf, dry_run: bool, output: Option<PathBuf>) -> Result<()> {
+    let mut toml_data = String::new();
+    File::open(&build_toml)
+        .wrap_err_with(|| format!("Cannot open {} for reading", build_toml.to_string_lossy()))?
+        .read_to_string(&mut toml_data)
+        .wrap_err_with(|| format!("Cannot read from {}", build_toml.to_string_lossy()))?;
+
+    let compat: BuildCompat = toml::from_str(&toml_data)
+        .wrap_err_with(|| format!("Cannot parse TOML in {}", build_toml.to_string_lossy()))?;
+
+    let was_v1 = matches!(compat, BuildCompat::V1(_));
+    let build: Build = compat.into();
+
+    // `[general]` serializes first since it has no sub-tables, `[torch]` and the
+    // per-kernel `[kernel.*]` tables serialize last since TOML requires tables to
+    // follow any simple key/value pairs in their parent table.
+    let mut migrated = toml::to_string_pretty(&build)
+        .wrap_err("Cannot serialize migrated build.toml to TOML")?;
+
+    if was_v1 {
+        migrated = format!(
+            "# Migrated from the v1 build.toml schema by `build2cmake migrate`.\n{migrated}"
+        );
+    }
+
+    if dry_run {
+        print!("{migrated}");
+        return Ok(());
+    }
+
+    let destination = output.unwrap_or_else(|| build_toml.clone());
+
+    if !was_v1 {
+        eprintln!(
+            "{} is already in the v2 schema; rewriting it in canonical form",
+            build_toml.to_string_lossy()
+        );
+    }
+
+    File::create(&destination)
+        .wrap_err_with(|| format!("Cannot open {} for writing", destination.to_string_lossy()))?
+        .write_all(migrated.as_bytes())
+        .wrap_err_with(|| format!("Cannot write to {}", destination.to_string_lossy()))?;
+
+    Ok(())
+}
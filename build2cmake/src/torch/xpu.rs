@@ -8,7 +8,7 @@ use itertools::Itertools;
 use minijinja::{context, Environment};
 
 use super::kernel_ops_identifier;
-use crate::config::{Build, Dependencies, Kernel, Torch};
+use crate::config::{Build, Dependencies, Kernel, Profile, Torch};
 use crate::FileSet;
 
 static CMAKE_UTILS: &str = include_str!("../templates/utils.cmake");
@@ -43,6 +43,7 @@ pub fn write_torch_ext_xpu(
         torch_ext,
         &build.general.name,
         &ops_name,
+        build.build.profile,
         &mut file_set,
     )?;
 
@@ -82,6 +83,7 @@ fn write_setup_py(
     torch: &Torch,
     name: &str,
     ops_name: &str,
+    profile: Profile,
     file_set: &mut FileSet,
 ) -> Result<()> {
     let writer = file_set.entry("setup.py");
@@ -96,6 +98,7 @@ fn write_setup_py(
                 ops_name => ops_name,
                 name => name,
                 version => "0.1.0",
+                cmake_build_type => profile.cmake_build_type(),
             },
             writer,
         )
@@ -157,10 +160,10 @@ fn write_cmake(
         .iter()
         .filter(|(_, kernel)| matches!(kernel, Kernel::Xpu { .. }))
     {
-        render_kernel(env, kernel_name, kernel, cmake_writer)?;
+        render_kernel(env, kernel_name, kernel, build.build.profile, cmake_writer)?;
     }
 
-    render_extension(env, ops_name, cmake_writer)?;
+    render_extension(env, ops_name, build.build.profile, cmake_writer)?;
 
     Ok(())
 }
@@ -226,6 +229,7 @@ pub fn render_kernel(
     env: &Environment,
     kernel_name: &str,
     kernel: &Kernel,
+    profile: Profile,
     write: &mut impl Write,
 ) -> Result<()> {
     // Easier to do in Rust than Jinja.
@@ -241,15 +245,46 @@ pub fn render_kernel(
         _ => unreachable!("Unsupported kernel type for XPU rendering"),
     };
 
+    let mut cxx_flags = kernel.cxx_flags().unwrap_or_default();
+    let mut sycl_flags = sycl_flags.unwrap_or_default().to_vec();
+    match profile {
+        Profile::Release => (),
+        Profile::Debug => {
+            cxx_flags.extend(["-O0".to_string(), "-g".to_string()]);
+            sycl_flags.push("-g".to_string());
+        }
+        Profile::RelWithDebInfo => {
+            cxx_flags.push("-g".to_string());
+            sycl_flags.push("-g".to_string());
+        }
+    }
+
+    // Sources listed in `debug-sources` carry debug info even in a `release`
+    // build, the same per-file override PyTorch's `USE_CUSTOM_DEBINFO` provides.
+    let debug_sources = kernel
+        .debug_sources()
+        .map(|sources| sources.iter().map(|src| format!("\"{src}\"")).join(" "));
+
+    // A kernel scoped to specific architectures (e.g. `cuda-capabilities = ["8.0",
+    // "9.0"]`) only gets compiled into the matching variant, via a guard in the
+    // template around this kernel's `target_sources` call, instead of a
+    // hand-written CMake `if()`.
+    let archs = kernel
+        .cuda_capabilities()
+        .or_else(|| kernel.rocm_archs())
+        .map(|archs| archs.join(";"));
+
     env.get_template("xpu/kernel.cmake")
         .wrap_err("Cannot get kernel template")?
         .render_to_write(
             context! {
-                cxx_flags => kernel.cxx_flags().map(|flags| flags.join(";")),
-                sycl_flags => sycl_flags.map(|flags| flags.join(";")),
+                cxx_flags => (!cxx_flags.is_empty()).then(|| cxx_flags.join(";")),
+                sycl_flags => (!sycl_flags.is_empty()).then(|| sycl_flags.join(";")),
                 includes => kernel.include().map(prefix_and_join_includes),
                 kernel_name => kernel_name,
                 sources => sources,
+                debug_sources => debug_sources,
+                archs => archs,
             },
             &mut *write,
         )
@@ -260,12 +295,18 @@ pub fn render_kernel(
     Ok(())
 }
 
-pub fn render_extension(env: &Environment, ops_name: &str, write: &mut impl Write) -> Result<()> {
+pub fn render_extension(
+    env: &Environment,
+    ops_name: &str,
+    profile: Profile,
+    write: &mut impl Write,
+) -> Result<()> {
     env.get_template("xpu/torch-extension.cmake")
         .wrap_err("Cannot get Torch extension template")?
         .render_to_write(
             context! {
                 ops_name => ops_name,
+                cmake_build_type => profile.cmake_build_type(),
             },
             &mut *write,
         )
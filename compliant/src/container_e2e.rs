@@ -0,0 +1,104 @@
+//! Opt-in end-to-end harness that runs a fixture kernel build through both the
+//! static `kernel-compliance-check` auditor and a real `manylinux_2_28` container,
+//! to close the gap between "the checker says it's compliant" and "it actually
+//! loads on the baseline platform."
+//!
+//! Disabled by default: building a kernel, pulling a multi-gigabyte container
+//! image, and running Docker aren't things a plain `cargo test` should require.
+//! Opt in with `cargo test --features container_tests -- --ignored`, with Docker
+//! available and `COMPLIANT_RUN_CONTAINER_TESTS=1` set.
+
+#![cfg(feature = "container_tests")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use kernel_abi_check::Version;
+
+/// Pinned so a test failure always means "this kernel regressed," never "the
+/// manylinux image moved under us."
+const MANYLINUX_IMAGE: &str = "quay.io/pypa/manylinux_2_28_x86_64:2024.05.20-1";
+const MANYLINUX_2_28_GLIBC: Version = Version {
+    major: 2,
+    minor: 28,
+    patch: 0,
+};
+
+#[test]
+#[ignore = "requires Docker and a built fixture kernel; run with --ignored"]
+fn test_container_backed_compliance_check() {
+    if std::env::var("COMPLIANT_RUN_CONTAINER_TESTS").as_deref() != Ok("1") {
+        eprintln!("skipping: set COMPLIANT_RUN_CONTAINER_TESTS=1 to run this test");
+        return;
+    }
+
+    let so_path = fixture_repo_dir().join("build/torch-universal/fixture_kernel.so");
+    assert!(
+        so_path.exists(),
+        "fixture kernel not built at {:?}; build it before running this test",
+        so_path
+    );
+
+    // What the static auditor thinks this shared object requires.
+    let data = std::fs::read(&so_path).expect("read fixture shared object");
+    let file = object::File::parse(&*data).expect("parse fixture shared object");
+    let required = kernel_abi_check::max_library_versions(&data, file.endianness())
+        .expect("collect symbol version requirements");
+    let glibc_requirement = required.get("GLIBC").cloned().unwrap_or(Version {
+        major: 2,
+        minor: 17,
+        patch: 0,
+    });
+    let auditor_says_compatible = glibc_requirement <= MANYLINUX_2_28_GLIBC;
+
+    // What actually happens when a manylinux_2_28 container tries to load it.
+    let container_result = run_in_manylinux_container(&so_path);
+
+    // A mismatch means the static pass and the real loader disagree, which is
+    // exactly the gap this harness exists to catch.
+    assert_eq!(
+        auditor_says_compatible, container_result.success,
+        "static auditor (compatible={}) and container load (succeeded={}) disagree:\n{}",
+        auditor_says_compatible, container_result.success, container_result.output
+    );
+}
+
+struct ContainerRunResult {
+    success: bool,
+    output: String,
+}
+
+/// Mount the fixture shared object into a pinned `manylinux_2_28` container and try
+/// to `dlopen` it with Python's `ctypes`, the same way a wheel consumer would.
+fn run_in_manylinux_container(so_path: &Path) -> ContainerRunResult {
+    let mount = format!("{}:/fixture.so:ro", so_path.display());
+
+    let output = Command::new("docker")
+        .args([
+            "run",
+            "--rm",
+            "-v",
+            &mount,
+            MANYLINUX_IMAGE,
+            "python3",
+            "-c",
+            "import ctypes; ctypes.CDLL('/fixture.so')",
+        ])
+        .output()
+        .expect("failed to run docker; is it installed and running?");
+
+    ContainerRunResult {
+        success: output.status.success(),
+        output: format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ),
+    }
+}
+
+/// Locate the prebuilt fixture kernel repository used by this test, analogous to
+/// `build2cmake`'s `../examples/relu` fixture.
+fn fixture_repo_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/container-kernel")
+}
@@ -0,0 +1,319 @@
+//! Resumable, range-based file downloads for the HF cache fetch path.
+//!
+//! A plain whole-file download restarts from zero on any interruption, which is
+//! especially painful for multi-gigabyte kernel `.so` artifacts on a flaky
+//! connection. This splits each file into fixed-size byte ranges, fetches them
+//! concurrently straight into their offset in a `.partial` file, and records
+//! which ranges have landed in a small sidecar so a re-run only re-fetches
+//! what's still missing. Once every range is down and the assembled file's
+//! hash matches what the Hub reports, the `.partial` file is renamed into
+//! place atomically.
+
+use std::collections::BTreeSet;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use futures::stream::{self, StreamExt};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use crate::integrity::{self, ExpectedDigest};
+
+/// Byte range size for each chunk: large enough to amortize per-request
+/// overhead, small enough that one flaky range doesn't waste much work.
+const CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+/// How many ranges to fetch concurrently, per file.
+const CHUNK_CONCURRENCY: usize = 8;
+/// Retries per range before giving up on the whole file.
+const MAX_RETRIES: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy)]
+struct ByteRange {
+    start: u64,
+    end: u64, // inclusive
+}
+
+impl ByteRange {
+    fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// Sidecar tracking which chunk indices have already landed on disk, so a
+/// re-run of `download_resumable` can skip straight to what's still missing.
+#[derive(Serialize, Deserialize, Default)]
+struct ResumeState {
+    completed_chunks: BTreeSet<usize>,
+}
+
+impl ResumeState {
+    fn load(path: &Path) -> Self {
+        std::fs::read(path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let data = serde_json::to_vec(self).context("Failed to serialize resume sidecar")?;
+        std::fs::write(path, data)
+            .with_context(|| format!("Failed to write resume sidecar: {:?}", path))
+    }
+}
+
+/// Download `url` into `dest`, resuming a previous interrupted attempt if a
+/// `.partial`/sidecar pair for it is still on disk. Falls back to a single
+/// whole-file GET when the server doesn't advertise range support. When
+/// `expected` is known, the assembled file is hashed and rejected (without
+/// being promoted to `dest`) if it doesn't match.
+pub async fn download_resumable(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    expected: Option<&ExpectedDigest>,
+) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+    }
+
+    let head = client
+        .head(url)
+        .send()
+        .await
+        .with_context(|| format!("HEAD request failed for {}", url))?;
+
+    let content_length = head
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+
+    let accepts_ranges = head
+        .headers()
+        .get(reqwest::header::ACCEPT_RANGES)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("bytes"));
+
+    let partial_path = with_suffix(dest, ".partial");
+
+    match content_length.filter(|_| accepts_ranges) {
+        Some(total_len) => download_in_chunks(client, url, dest, &partial_path, total_len).await?,
+        None => download_whole(client, url, &partial_path).await?,
+    }
+
+    if let Some(expected) = expected {
+        let actual = integrity::compute_digest(&partial_path, expected)
+            .with_context(|| format!("Failed to hash downloaded file: {:?}", partial_path))?;
+        if actual != expected.as_str() {
+            let _ = std::fs::remove_file(&partial_path);
+            bail!(
+                "downloaded file {:?} does not match the Hub's reported hash (expected {}, got {})",
+                dest,
+                expected.as_str(),
+                actual
+            );
+        }
+    }
+
+    tokio::fs::rename(&partial_path, dest)
+        .await
+        .with_context(|| format!("Failed to rename {:?} to {:?}", partial_path, dest))?;
+
+    Ok(())
+}
+
+async fn download_in_chunks(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    partial_path: &Path,
+    total_len: u64,
+) -> Result<()> {
+    let sidecar_path = with_suffix(dest, ".partial.ranges");
+    let ranges = chunk_ranges(total_len);
+
+    // Pre-size the partial file so every chunk can seek straight to its own
+    // offset regardless of completion order.
+    {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(partial_path)
+            .await
+            .with_context(|| format!("Failed to open partial file: {:?}", partial_path))?;
+        file.set_len(total_len)
+            .await
+            .with_context(|| format!("Failed to pre-size partial file: {:?}", partial_path))?;
+    }
+
+    let mut state = ResumeState::load(&sidecar_path);
+    let pending: Vec<(usize, ByteRange)> = ranges
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !state.completed_chunks.contains(index))
+        .collect();
+
+    // Persist `state` after each chunk lands rather than collecting every future
+    // into a `Vec` first and saving only once the whole batch resolves: a hard
+    // kill mid-download would otherwise lose every chunk's completion record even
+    // though the bytes for already-finished chunks are sitting correctly on disk,
+    // forcing a full re-download on the next run instead of a resume.
+    let mut first_err: Option<anyhow::Error> = None;
+    stream::iter(pending)
+        .map(|(index, range)| {
+            let client = client.clone();
+            let url = url.to_string();
+            let partial_path = partial_path.to_path_buf();
+            async move {
+                let result = download_chunk_with_retry(&client, &url, &partial_path, range).await;
+                (index, result)
+            }
+        })
+        .buffer_unordered(CHUNK_CONCURRENCY)
+        .for_each(|(index, result)| {
+            match result {
+                Ok(()) => {
+                    state.completed_chunks.insert(index);
+                    if let Err(e) = state.save(&sidecar_path) {
+                        first_err.get_or_insert(e);
+                    }
+                }
+                Err(e) => {
+                    first_err.get_or_insert(e);
+                }
+            }
+            futures::future::ready(())
+        })
+        .await;
+
+    if let Some(e) = first_err {
+        return Err(e);
+    }
+
+    let _ = std::fs::remove_file(&sidecar_path);
+    Ok(())
+}
+
+async fn download_chunk_with_retry(
+    client: &Client,
+    url: &str,
+    partial_path: &Path,
+    range: ByteRange,
+) -> Result<()> {
+    let mut attempt = 0;
+    let mut backoff = INITIAL_BACKOFF;
+    loop {
+        match download_chunk(client, url, partial_path, range).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < MAX_RETRIES => {
+                eprintln!(
+                    "warning: range {}-{} of {} failed (attempt {}/{}): {}; retrying in {:?}",
+                    range.start,
+                    range.end,
+                    url,
+                    attempt + 1,
+                    MAX_RETRIES + 1,
+                    e,
+                    backoff
+                );
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+                backoff *= 2;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!(
+                        "giving up on range {}-{} of {} after {} attempts",
+                        range.start, range.end, url, MAX_RETRIES + 1
+                    )
+                })
+            }
+        }
+    }
+}
+
+async fn download_chunk(client: &Client, url: &str, partial_path: &Path, range: ByteRange) -> Result<()> {
+    let response = client
+        .get(url)
+        .header(
+            reqwest::header::RANGE,
+            format!("bytes={}-{}", range.start, range.end),
+        )
+        .send()
+        .await
+        .with_context(|| format!("Range request failed for {}", url))?
+        .error_for_status()
+        .with_context(|| format!("Range request returned an error status for {}", url))?;
+
+    let bytes = response
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to read range body for {}", url))?;
+
+    if bytes.len() as u64 != range.len() {
+        bail!(
+            "short range read: expected {} bytes, got {}",
+            range.len(),
+            bytes.len()
+        );
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(partial_path)
+        .await
+        .with_context(|| format!("Failed to open partial file: {:?}", partial_path))?;
+    file.seek(SeekFrom::Start(range.start))
+        .await
+        .with_context(|| format!("Failed to seek in partial file: {:?}", partial_path))?;
+    file.write_all(&bytes)
+        .await
+        .with_context(|| format!("Failed to write range to partial file: {:?}", partial_path))?;
+
+    Ok(())
+}
+
+/// Used when the server doesn't advertise range support (no `Content-Length`,
+/// or `Accept-Ranges` isn't `bytes`).
+async fn download_whole(client: &Client, url: &str, partial_path: &Path) -> Result<()> {
+    let bytes = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("GET request failed for {}", url))?
+        .error_for_status()
+        .with_context(|| format!("GET request returned an error status for {}", url))?
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to read response body for {}", url))?;
+
+    tokio::fs::write(partial_path, &bytes)
+        .await
+        .with_context(|| format!("Failed to write {:?}", partial_path))?;
+
+    Ok(())
+}
+
+fn chunk_ranges(total_len: u64) -> Vec<ByteRange> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < total_len {
+        let end = (start + CHUNK_SIZE - 1).min(total_len - 1);
+        ranges.push(ByteRange { start, end });
+        start = end + 1;
+    }
+    ranges
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
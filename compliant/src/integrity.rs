@@ -0,0 +1,138 @@
+//! Verifies that locally cached repository files match the content hashes the
+//! Hub reports for them.
+//!
+//! `fetch_repository_async` only checks that `refs/<rev>` and a `build/`
+//! directory exist; it never validates that a download actually completed
+//! intact. A rate-limited or truncated transfer can leave a corrupt `.so` on
+//! disk that `check_shared_object` then fails to parse with a confusing error
+//! deep inside ABI checking, far from the download that actually caused it.
+//! This mirrors the Hub's own "verify" workflow: LFS files are compared by
+//! sha256, small git-tracked files by git blob sha1.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use hf_hub::api::tokio::ApiBuilder;
+use hf_hub::{Repo, RepoType};
+use sha1::{Digest as _, Sha1};
+use sha2::Sha256;
+
+/// A single file whose on-disk content didn't match what the Hub reports for it.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct IntegrityMismatch {
+    pub file: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+pub(crate) enum ExpectedDigest {
+    /// LFS-tracked files are content-addressed by sha256.
+    Sha256(String),
+    /// Small, non-LFS files are tracked as regular git blobs, hashed as
+    /// `sha1("blob {len}\0" + content)`.
+    GitBlobSha1(String),
+}
+
+impl ExpectedDigest {
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            ExpectedDigest::Sha256(s) | ExpectedDigest::GitBlobSha1(s) => s,
+        }
+    }
+}
+
+/// Compare every sibling file present in `snapshot_dir` against the digest the
+/// Hub reports for it at `revision`. Files that aren't present locally (e.g.
+/// excluded by an allow/ignore pattern) are skipped rather than flagged.
+pub async fn verify_repository(
+    repo_id: &str,
+    snapshot_dir: &Path,
+    revision: &str,
+) -> Result<Vec<IntegrityMismatch>> {
+    let api = ApiBuilder::new()
+        .high()
+        .build()
+        .context("Failed to create HF API client")?;
+    let repo = Repo::with_revision(repo_id.to_string(), RepoType::Model, revision.to_string());
+    let info = api
+        .repo(repo)
+        .info()
+        .await
+        .with_context(|| format!("Failed to fetch repo info for {}", repo_id))?;
+
+    let mut mismatches = Vec::new();
+    for sibling in &info.siblings {
+        let local_path = snapshot_dir.join(&sibling.rfilename);
+        if !local_path.exists() {
+            continue;
+        }
+
+        let Some(expected) = expected_digest(sibling) else {
+            continue;
+        };
+
+        let actual = compute_digest(&local_path, &expected)
+            .with_context(|| format!("Failed to hash local file: {:?}", local_path))?;
+
+        if actual != expected.as_str() {
+            mismatches.push(IntegrityMismatch {
+                file: sibling.rfilename.clone(),
+                expected: expected.as_str().to_string(),
+                actual,
+            });
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Re-download just the named files with hf-hub's `ApiRepo::download`, which
+/// fetches each file whole rather than through the resumable range download
+/// `fetch_repository_async` uses, so a caller can repair a single corrupt file
+/// without re-fetching the whole repository.
+pub async fn redownload_files(repo_id: &str, revision: &str, files: &[String]) -> Result<()> {
+    let api = ApiBuilder::new()
+        .high()
+        .build()
+        .context("Failed to create HF API client")?;
+    let repo = Repo::with_revision(repo_id.to_string(), RepoType::Model, revision.to_string());
+    let api_repo = api.repo(repo);
+
+    for file_name in files {
+        api_repo
+            .download(file_name)
+            .await
+            .with_context(|| format!("Failed to re-download {}", file_name))?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn expected_digest(sibling: &hf_hub::api::Siblings) -> Option<ExpectedDigest> {
+    if let Some(lfs) = &sibling.lfs {
+        Some(ExpectedDigest::Sha256(lfs.sha256.clone()))
+    } else {
+        sibling.blob_id.clone().map(ExpectedDigest::GitBlobSha1)
+    }
+}
+
+pub(crate) fn compute_digest(path: &Path, expected: &ExpectedDigest) -> Result<String> {
+    let data = fs::read(path).with_context(|| format!("Failed to read file: {:?}", path))?;
+
+    let digest = match expected {
+        ExpectedDigest::Sha256(_) => {
+            let mut hasher = Sha256::new();
+            hasher.update(&data);
+            hex::encode(hasher.finalize())
+        }
+        ExpectedDigest::GitBlobSha1(_) => {
+            let mut hasher = Sha1::new();
+            hasher.update(format!("blob {}\0", data.len()));
+            hasher.update(&data);
+            hex::encode(hasher.finalize())
+        }
+    };
+
+    Ok(digest)
+}
@@ -1,17 +1,37 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use colored::Colorize;
-use hf_hub::api::tokio::{ApiBuilder, ApiError};
+use hf_hub::api::tokio::ApiBuilder;
 use hf_hub::{Repo, RepoType};
 use kernel_abi_check::{check_manylinux, check_python_abi, Version};
-use object::Object;
+use memmap2::Mmap;
+use object::{Object, ObjectSegment};
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
 use thiserror::Error;
 
+mod serve;
+pub use serve::run_server;
+
+mod lockfile;
+
+mod integrity;
+
+mod download;
+
+mod policy;
+pub use policy::{CompliancePolicy, Severity};
+
+#[cfg(test)]
+mod container_e2e;
+
 #[derive(Error, Debug)]
 pub enum CompliantError {
     #[error("IO error: {0}")]
@@ -26,12 +46,22 @@ pub enum CompliantError {
     #[error("Failed to fetch repository: {0}")]
     FetchError(String),
 
+    #[error("Integrity check failed for {file}: expected {expected}, got {actual}")]
+    IntegrityError {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+
     #[error("Failed to parse object file: {0}")]
     ObjectParseError(String),
 
     #[error("Failed to check ABI compatibility: {0}")]
     AbiCheckError(String),
 
+    #[error("Failed to determine musllinux compatibility: {0}")]
+    MusllinuxCheckError(String),
+
     #[error("Failed to serialize JSON: {0}")]
     SerializationError(String),
 
@@ -45,6 +75,31 @@ pub enum CompliantError {
     Other(String),
 }
 
+impl CompliantError {
+    /// A short, actionable suggestion for resolving this error, printed below the
+    /// error message the way a compiler prints a "help:" line.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            CompliantError::RepositoryNotFound(_) => Some(
+                "re-run with --auto-fetch to fetch the repository, or check that --revision matches an existing branch, tag, or commit",
+            ),
+            CompliantError::BuildDirNotFound(_) => Some(
+                "this repository may not ship precompiled variants; check its `build` directory on the Hub",
+            ),
+            CompliantError::AbiCheckError(_) => Some(
+                "try lowering --manylinux (e.g. manylinux_2_28 to manylinux_2_17) or raising --python-abi",
+            ),
+            CompliantError::FetchError(_) | CompliantError::NetworkError(_) => Some(
+                "check your network connection and that the repository ID is correct",
+            ),
+            CompliantError::IntegrityError { .. } => Some(
+                "re-run `verify` with --redownload to re-fetch the affected file(s)",
+            ),
+            _ => None,
+        }
+    }
+}
+
 /// Hugging Face kernel compliance checker
 #[derive(Parser)]
 #[command(author, version, about)]
@@ -57,12 +112,35 @@ pub struct Cli {
 pub enum Format {
     Console,
     Json,
+    /// Machine-readable compliance report including detected platform tags, suitable
+    /// for tracking ABI drift between revisions in CI.
+    Sbom,
+    /// JUnit XML, one `<testsuite>` per repository and one `<testcase>` per build
+    /// variant, so CI systems can surface a failing check as a named test rather
+    /// than buried in stdout.
+    Junit,
+    /// SARIF 2.1.0, one `run` per repository with one `result` per ABI violation
+    /// or missing build variant, so GitHub/GitLab code-scanning can annotate the
+    /// offending shared object inline instead of parsing our custom JSON.
+    Sarif,
 }
 
 impl Format {
     pub fn is_json(&self) -> bool {
         matches!(self, Format::Json)
     }
+
+    pub fn is_sbom(&self) -> bool {
+        matches!(self, Format::Sbom)
+    }
+
+    pub fn is_junit(&self) -> bool {
+        matches!(self, Format::Junit)
+    }
+
+    pub fn is_sarif(&self) -> bool {
+        matches!(self, Format::Sarif)
+    }
 }
 
 #[derive(Subcommand)]
@@ -80,14 +158,27 @@ pub enum Commands {
         #[arg(short, long)]
         repos: String,
 
-        /// Manylinux version to check against
+        /// Manylinux version(s) to check against, comma-separated (e.g.
+        /// `manylinux_2_17,manylinux_2_28`). More than one, or more than one
+        /// `--python-abi`, switches to a compliance matrix across every combination.
         #[arg(short, long, default_value = "manylinux_2_28")]
         manylinux: String,
 
-        /// Python ABI version to check against
+        /// Musllinux version to check against. When given, shared objects are probed
+        /// against the musl dynamic loader instead of glibc and `--manylinux` is ignored.
+        #[arg(long)]
+        musllinux: Option<String>,
+
+        /// Python ABI version(s) to check against, comma-separated (e.g. `3.9,3.12`).
+        /// More than one, or more than one `--manylinux`, switches to a compliance
+        /// matrix across every combination.
         #[arg(short, long, default_value = "3.9")]
         python_abi: String,
 
+        /// Minimum macOS version to check macOS (Mach-O) variants against
+        #[arg(long, default_value = "11.0")]
+        macos_min: String,
+
         /// Automatically fetch repositories if not found locally
         #[arg(short, long, default_value = "true")]
         auto_fetch: bool,
@@ -104,6 +195,157 @@ pub enum Commands {
         #[arg(long, default_value = "false")]
         show_violations: bool,
 
+        /// Bypass the content-addressed ABI check cache and re-check every shared object.
+        #[arg(long, default_value = "false")]
+        no_cache: bool,
+
+        /// Verify cached files against the Hub's reported content hashes before
+        /// checking ABI compatibility, so a truncated download fails loudly here
+        /// instead of as a confusing parse error further in.
+        #[arg(long, default_value = "false")]
+        verify: bool,
+
+        /// Golden-file path pinning expected compliance state. On the first run, the
+        /// current result is written there; on later runs, the current result is
+        /// compared against it and a regression fails the check.
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// Overwrite the `--baseline` file with the current result instead of
+        /// comparing against it.
+        #[arg(long, default_value = "false")]
+        bless: bool,
+
+        /// Path to a `compliance.toml` policy manifest declaring this repository's
+        /// required CUDA/ROCm variants and failure severities. Defaults to a
+        /// `compliance.toml` at the snapshot root if one is present, else the
+        /// built-in variant list, with missing variants and ABI violations
+        /// reported but not failing the process.
+        #[arg(long)]
+        policy: Option<String>,
+
+        /// Format of the output. Default is console
+        #[arg(long, default_value = "console")]
+        format: Format,
+
+        /// Number of worker threads to check variants and shared objects with.
+        /// Defaults to the available parallelism.
+        #[arg(short = 'j', long)]
+        jobs: Option<usize>,
+    },
+
+    /// Serve compliance checks over HTTP as `GET /check?repos=...`
+    Serve {
+        /// Address to bind the server to
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+
+        /// Port to listen on
+        #[arg(long, default_value = "8080")]
+        port: u16,
+
+        /// Maximum number of repository checks to run concurrently
+        #[arg(long, default_value = "4")]
+        concurrency: usize,
+
+        /// Bearer token callers must present as `Authorization: Bearer <token>`.
+        /// Required when `--bind` is anything other than a loopback address, since
+        /// `/check` drives ABI audits (including musllinux loader detection) against
+        /// attacker-named repository content and must not be reachable anonymously
+        /// from the network. May also be set via `KERNEL_BUILDER_SERVE_TOKEN`.
+        #[arg(long, env = "KERNEL_BUILDER_SERVE_TOKEN")]
+        auth_token: Option<String>,
+    },
+
+    /// Compare ABI compliance for a repository between two revisions, so CI can
+    /// gate on newly introduced violations rather than the absolute count
+    Diff {
+        /// Repository ID or name
+        #[arg(short, long)]
+        repo: String,
+
+        /// Older revision (branch, tag, or commit hash)
+        #[arg(long, default_value = "main")]
+        revision_a: String,
+
+        /// Newer revision (branch, tag, or commit hash) to compare against `--revision-a`
+        #[arg(long)]
+        revision_b: String,
+
+        /// Manylinux version to check both revisions against
+        #[arg(short, long, default_value = "manylinux_2_28")]
+        manylinux: String,
+
+        /// Musllinux version to check both revisions against. When given, shared
+        /// objects are probed against the musl dynamic loader instead of glibc and
+        /// `--manylinux` is ignored.
+        #[arg(long)]
+        musllinux: Option<String>,
+
+        /// Python ABI version to check both revisions against
+        #[arg(short, long, default_value = "3.9")]
+        python_abi: String,
+
+        /// Minimum macOS version to check macOS (Mach-O) variants against
+        #[arg(long, default_value = "11.0")]
+        macos_min: String,
+
+        /// Automatically fetch either revision if not found locally
+        #[arg(short, long, default_value = "true")]
+        auto_fetch: bool,
+
+        /// Bypass the content-addressed ABI check cache and re-check every shared object.
+        #[arg(long, default_value = "false")]
+        no_cache: bool,
+
+        /// Format of the output. Default is console
+        #[arg(long, default_value = "console")]
+        format: Format,
+    },
+
+    /// Verify that cached repository files match the content hashes the Hub
+    /// reports for them, so a truncated or corrupted download is caught before
+    /// it produces a confusing parse error during ABI checking
+    Verify {
+        /// Repository ID or name
+        #[arg(short, long)]
+        repo: String,
+
+        /// Revision (branch, tag, or commit hash) to verify against
+        #[arg(short, long, default_value = "main")]
+        revision: String,
+
+        /// Automatically fetch the repository if not found locally
+        #[arg(short, long, default_value = "true")]
+        auto_fetch: bool,
+
+        /// Re-download any file that fails verification and check it again
+        #[arg(long, default_value = "false")]
+        redownload: bool,
+
+        /// Format of the output. Default is console
+        #[arg(long, default_value = "console")]
+        format: Format,
+    },
+
+    /// List which expected CUDA/ROCm build variants are missing from a
+    /// repository, without an ABI check. The expected lists come from
+    /// `COMPLIANT_VARIANTS`, which downloads `build-variants.json` on first use,
+    /// and a snapshot that isn't cached is fetched when --auto-fetch is set
+    ListMissing {
+        /// Repository IDs or names (comma-separated)
+        #[arg(short, long)]
+        repos: String,
+
+        /// Revision (branch, tag, or commit hash) whose local snapshot to inspect
+        #[arg(short, long, default_value = "main")]
+        revision: String,
+
+        /// Fetch the repository if no local snapshot exists yet. Never re-fetches
+        /// an already-present snapshot.
+        #[arg(short, long, default_value = "false")]
+        auto_fetch: bool,
+
         /// Format of the output. Default is console
         #[arg(long, default_value = "console")]
         format: Format,
@@ -243,6 +485,12 @@ impl ConsoleFormatter {
         println!("╰── abi: missing");
     }
 
+    /// Print an actionable suggestion below an error, the way a compiler prints a
+    /// "help:" line.
+    pub fn format_hint(hint: &str) {
+        println!("{} {}", "help:".cyan().bold(), hint);
+    }
+
     pub fn format_fetch_status(repo_id: &str, fetching: bool, result: Option<&str>) {
         println!("repository: {}", repo_id);
         if fetching {
@@ -269,6 +517,7 @@ impl ConsoleFormatter {
         compact_output: bool,
         abi_output: &AbiCheckResult,
         abi_status: &str,
+        show_violations: bool,
     ) {
         // Display console-formatted output
         let abi_mark = if abi_output.overall_compatible {
@@ -354,9 +603,176 @@ impl ConsoleFormatter {
         println!("╰── abi: {}", abi_status);
         println!("    ├── {} {}", abi_mark, abi_output.manylinux_version);
         println!(
-            "    ╰── {} python {}",
+            "    ├── {} python {}",
             abi_mark, abi_output.python_abi_version
         );
+        println!(
+            "    ╰── {} macos-min {}",
+            abi_mark, abi_output.macos_min_version
+        );
+
+        if show_violations {
+            for variant in &abi_output.variants {
+                if !variant.violations.is_empty() || !variant.waived.is_empty() {
+                    println!(
+                        "        {} {} violation(s), {} waived by policy",
+                        variant.name,
+                        variant.violations.len(),
+                        variant.waived.len()
+                    );
+
+                    let maxima: Vec<String> = [
+                        variant.max_glibc.as_ref().map(|v| format!("glibc {v}")),
+                        variant.max_glibcxx.as_ref().map(|v| format!("glibcxx {v}")),
+                        variant.max_cxxabi.as_ref().map(|v| format!("cxxabi {v}")),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+
+                    if !maxima.is_empty() {
+                        println!("          max required: {}", maxima.join(", "));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Print a `DiffResult` as a tree of variant statuses, one line per variant plus
+    /// any violations newly introduced or resolved underneath it.
+    pub fn format_diff_result(diff: &DiffResult) {
+        let label = format!(" {} ", diff.repository)
+            .black()
+            .on_bright_white()
+            .bold();
+
+        println!("\n{}", label);
+        println!("├── {} -> {}", diff.revision_a, diff.revision_b);
+
+        for (i, variant) in diff.variants.iter().enumerate() {
+            let is_last = i == diff.variants.len() - 1;
+            let branch = if is_last { "╰── " } else { "├── " };
+            let continuation = if is_last { "    " } else { "│   " };
+
+            let (mark, status_label) = match variant.status {
+                VariantDiffStatus::Added => ("+".green(), "added"),
+                VariantDiffStatus::Removed => ("-".red(), "removed"),
+                VariantDiffStatus::Unchanged => ("=".dimmed(), "unchanged"),
+                VariantDiffStatus::Regressed => ("✗".red(), "regressed"),
+                VariantDiffStatus::Fixed => ("✓".green(), "fixed"),
+            };
+
+            println!("{}{} {} ({})", branch, mark, variant.name, status_label);
+
+            for violation in &variant.violations_added {
+                println!("{}    + {}", continuation, violation);
+            }
+            for violation in &variant.violations_resolved {
+                println!("{}    - {}", continuation, violation);
+            }
+        }
+    }
+
+    /// Print a `ComplianceMatrix` as a tree: one branch per build variant, with a
+    /// leaf per policy/Python-ABI cell showing pass/fail and how many violations it
+    /// hit.
+    pub fn format_compliance_matrix(matrix: &ComplianceMatrix) {
+        let label = format!(" {} ", matrix.repository)
+            .black()
+            .on_bright_white()
+            .bold();
+
+        println!("\n{}", label);
+
+        for (i, row) in matrix.rows.iter().enumerate() {
+            let is_last_row = i == matrix.rows.len() - 1;
+            let branch = if is_last_row { "╰── " } else { "├── " };
+            let continuation = if is_last_row { "    " } else { "│   " };
+
+            println!("{}{}", branch, row.variant);
+
+            for (j, cell) in row.cells.iter().enumerate() {
+                let is_last_cell = j == row.cells.len() - 1;
+                let cell_branch = if is_last_cell { "╰── " } else { "├── " };
+                let mark = if cell.compatible {
+                    "✓".green()
+                } else {
+                    "✗".red()
+                };
+                let detail = if cell.compatible || !matrix.show_violations {
+                    String::new()
+                } else {
+                    format!(" ({} violation(s))", cell.violation_count)
+                };
+
+                println!(
+                    "{}{}{} {}/{}{}",
+                    continuation, cell_branch, mark, cell.policy, cell.python_abi, detail
+                );
+            }
+        }
+    }
+
+    /// Print which expected CUDA/ROCm variants a repository is missing, one
+    /// branch per backend with the missing variant names as leaves.
+    pub fn format_missing_variants(report: &MissingVariantsReport) {
+        let label = format!(" {} ", report.repository)
+            .black()
+            .on_bright_white()
+            .bold();
+        println!("\n{}", label);
+
+        let has_rocm = report.rocm.is_some();
+        let cuda_branch = if has_rocm { "├── " } else { "╰── " };
+        Self::format_missing_backend(cuda_branch, "CUDA", &report.cuda.missing, !has_rocm);
+
+        if let Some(rocm) = &report.rocm {
+            Self::format_missing_backend("╰── ", "ROCm", &rocm.missing, true);
+        }
+    }
+
+    fn format_missing_backend(branch: &str, backend: &str, missing: &[String], is_last: bool) {
+        if missing.is_empty() {
+            println!("{}{} {}: none missing", branch, "✓".green(), backend);
+            return;
+        }
+
+        println!("{}{} {}:", branch, "✗".red(), backend);
+        let continuation = if is_last { "    " } else { "│   " };
+        for (i, variant) in missing.iter().enumerate() {
+            let leaf = if i == missing.len() - 1 {
+                "╰── "
+            } else {
+                "├── "
+            };
+            println!("{}{}{}", continuation, leaf, variant);
+        }
+    }
+
+    /// Print the outcome of verifying a repository's cached files against the
+    /// Hub's reported content hashes: either a one-line "all good", or one line
+    /// per mismatched file.
+    pub fn format_integrity_report(repo_id: &str, mismatches: &[integrity::IntegrityMismatch]) {
+        let label = format!(" {} ", repo_id).black().on_bright_white().bold();
+        println!("\n{}", label);
+
+        if mismatches.is_empty() {
+            println!("╰── ✓ all cached files match the Hub's reported hashes");
+            return;
+        }
+
+        for (i, mismatch) in mismatches.iter().enumerate() {
+            let is_last = i == mismatches.len() - 1;
+            let branch = if is_last { "╰── " } else { "├── " };
+            println!(
+                "{}{} {} (expected {}, got {})",
+                branch,
+                "✗".red(),
+                mismatch.file,
+                mismatch.expected,
+                mismatch.actual
+            );
+        }
     }
 }
 
@@ -365,9 +781,11 @@ pub struct RepoErrorResponse {
     repository: String,
     status: String,
     error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<&'static str>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct RepositoryCheckResult {
     repository: String,
     status: String,
@@ -375,7 +793,7 @@ pub struct RepositoryCheckResult {
     abi_status: AbiStatus,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct BuildStatus {
     summary: String,
     cuda: CudaStatus,
@@ -383,159 +801,642 @@ pub struct BuildStatus {
     rocm: Option<RocmStatus>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct CudaStatus {
     compatible: bool,
     present: Vec<String>,
     missing: Vec<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct RocmStatus {
     compatible: bool,
     present: Vec<String>,
     missing: Vec<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct AbiStatus {
     compatible: bool,
     manylinux_version: String,
     python_abi_version: String,
+    macos_min_version: String,
     variants: Vec<VariantCheckOutput>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VariantCheckOutput {
     name: String,
     compatible: bool,
     has_shared_objects: bool,
-    violations: Vec<String>,
+    platform: String,
+    violations: Vec<SharedObjectViolation>,
+    /// Violations that would otherwise have failed this variant, but matched an
+    /// `AbiExceptions` entry from a `compliance.toml` policy.
+    waived: Vec<SharedObjectViolation>,
+    /// The highest `GLIBC`/`GLIBCXX`/`CXXABI` version this variant's shared
+    /// objects actually require, so a caller can see how far over a policy
+    /// ceiling a failing variant is, not just that it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_glibc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_glibcxx: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_cxxabi: Option<String>,
 }
 
-pub fn get_cache_dir() -> Result<PathBuf> {
-    let cache_dir = if let Ok(dir) = std::env::var("HF_KERNELS_CACHE") {
-        PathBuf::from(dir)
-    } else {
-        dirs::home_dir()
-            .unwrap_or_else(std::env::temp_dir)
-            .join(".cache/huggingface/hub")
-    };
+/// One JUnit `<testsuite>`: a repository's compliance result rendered as a tree of
+/// `<testcase>` elements, one per build variant (or, if the repository itself
+/// couldn't be checked, a single `<testcase>` describing why).
+struct JunitTestsuite {
+    name: String,
+    testcases: Vec<JunitTestcase>,
+}
 
-    if !cache_dir.exists() {
-        fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
-    }
+struct JunitTestcase {
+    classname: String,
+    name: String,
+    failure: Option<JunitFailure>,
+}
 
-    Ok(cache_dir)
+struct JunitFailure {
+    message: String,
+    body: String,
 }
 
-/// Get "org/name" repo ID from filesystem path
-pub fn get_repo_id_from_path(path: &Path) -> Result<String> {
-    // Extract the organization and model name from the path
-    let dir_name = path
-        .file_name()
-        .ok_or_else(|| CompliantError::Other(format!("Invalid path: {:?}", path)))?
-        .to_string_lossy()
-        .to_string();
+/// Render a repository's compliance result as a JUnit testsuite: one testcase per
+/// build variant, with ABI violations becoming the body of a `<failure>` that names
+/// the offending symbol or library and its required version.
+fn build_junit_testsuite(repo_id: &str, check_result: &RepositoryCheckResult) -> JunitTestsuite {
+    let testcases = check_result
+        .abi_status
+        .variants
+        .iter()
+        .map(|variant| {
+            let failure = if variant.compatible {
+                None
+            } else {
+                let body = variant
+                    .violations
+                    .iter()
+                    .map(|v| match (&v.required, &v.allowed) {
+                        (Some(required), Some(allowed)) => format!(
+                            "{}: requires {}, allowed {}",
+                            v.subject, required, allowed
+                        ),
+                        _ => v.message.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                Some(JunitFailure {
+                    message: if variant.waived.is_empty() {
+                        format!("{} ABI violation(s)", variant.violations.len())
+                    } else {
+                        format!(
+                            "{} ABI violation(s), {} waived by policy",
+                            variant.violations.len(),
+                            variant.waived.len()
+                        )
+                    },
+                    body,
+                })
+            };
 
-    // Remove the "models--" prefix if present
-    let dir_name = dir_name
-        .strip_prefix("models--")
-        .unwrap_or(&dir_name)
-        .replace("--", "/");
+            JunitTestcase {
+                classname: repo_id.to_string(),
+                name: variant.name.clone(),
+                failure,
+            }
+        })
+        .collect();
 
-    Ok(dir_name)
+    JunitTestsuite {
+        name: repo_id.to_string(),
+        testcases,
+    }
 }
 
-/// Check if repository has build variants
-pub fn has_build_variants(repo_path: &Path) -> Result<bool> {
-    // Look for the snapshot directory
-    let ref_file = repo_path.join("refs/main");
-    if !ref_file.exists() {
-        return Ok(false);
+/// A single-testcase testsuite for a repository that failed before any build
+/// variant could be checked (not found locally, fetch failure, missing snapshot or
+/// build directory).
+fn render_junit_error_testsuite(repo_id: &str, message: &str) -> JunitTestsuite {
+    JunitTestsuite {
+        name: repo_id.to_string(),
+        testcases: vec![JunitTestcase {
+            classname: repo_id.to_string(),
+            name: "repository".to_string(),
+            failure: Some(JunitFailure {
+                message: message.to_string(),
+                body: String::new(),
+            }),
+        }],
     }
+}
 
-    let content = fs::read_to_string(&ref_file)
-        .with_context(|| format!("Failed to read ref file: {:?}", ref_file))?;
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
 
-    let hash = content.trim();
-    let snapshot_dir = repo_path.join(format!("snapshots/{}", hash));
+/// Render a `JunitTestsuite` as the `<testsuite>`/`<testcase>` XML fragment CI
+/// systems (GitLab, Jenkins, GitHub Actions via a JUnit reporter) expect.
+fn render_junit_testsuite(suite: &JunitTestsuite) -> String {
+    let failures = suite
+        .testcases
+        .iter()
+        .filter(|tc| tc.failure.is_some())
+        .count();
 
-    if !snapshot_dir.exists() {
-        return Ok(false);
-    }
+    let mut xml = format!(
+        "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+        xml_escape(&suite.name),
+        suite.testcases.len(),
+        failures
+    );
 
-    // Check build directory
-    let build_dir = snapshot_dir.join("build");
-    if !build_dir.exists() {
-        return Ok(false);
+    for testcase in &suite.testcases {
+        xml.push_str(&format!(
+            "  <testcase classname=\"{}\" name=\"{}\">",
+            xml_escape(&testcase.classname),
+            xml_escape(&testcase.name)
+        ));
+
+        match &testcase.failure {
+            Some(failure) => {
+                xml.push('\n');
+                xml.push_str(&format!(
+                    "    <failure message=\"{}\">{}</failure>\n",
+                    xml_escape(&failure.message),
+                    xml_escape(&failure.body)
+                ));
+                xml.push_str("  </testcase>\n");
+            }
+            None => xml.push_str("</testcase>\n"),
+        }
     }
 
-    // Check if build directory has any variant subdirectories
-    let entries = fs::read_dir(&build_dir)
-        .with_context(|| format!("Failed to read build directory: {:?}", build_dir))?;
+    xml.push_str("</testsuite>");
+    xml
+}
 
-    for entry in entries {
-        let entry = entry.context("Failed to read directory entry")?;
-        let path = entry.path();
+/// A SARIF 2.1.0 log: one `run` carrying every ABI violation and missing-variant
+/// finding for a repository as a `result`, so GitHub/GitLab code-scanning can
+/// annotate the offending shared object inline instead of parsing our custom JSON.
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: String,
+    version: String,
+    runs: Vec<SarifRun>,
+}
 
-        if path.is_dir() {
-            // At least one build variant exists
-            return Ok(true);
-        }
-    }
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
 
-    // Build directory exists but is empty
-    Ok(false)
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
 }
 
-pub fn get_repo_path(repo_id: &str, base_dir: &Path) -> PathBuf {
-    let repo = Repo::with_revision(repo_id.to_string(), RepoType::Model, "main".to_string());
-    base_dir.join(repo.folder_name())
+#[derive(Serialize)]
+struct SarifDriver {
+    name: String,
+    rules: Vec<SarifRule>,
 }
 
-pub async fn fetch_repository_async(repo_id: &str, revision: &str) -> Result<()> {
-    let api = ApiBuilder::new()
-        .high()
-        .build()
-        .context("Failed to create HF API client")?;
+#[derive(Serialize)]
+struct SarifRule {
+    id: String,
+}
 
-    let repo = Repo::with_revision(repo_id.to_string(), RepoType::Model, revision.to_string());
+#[derive(Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: String,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+}
 
-    let api_repo = api.repo(repo);
-    let info = api_repo
-        .info()
-        .await
-        .context(format!("Failed to fetch repo info for {}", repo_id))?;
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
 
-    let file_names = info
-        .siblings
-        .iter()
-        .map(|f| f.rfilename.clone())
-        .collect::<Vec<_>>();
+#[derive(Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+const SARIF_SCHEMA_URI: &str =
+    "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
+
+/// Classify a `SharedObjectViolation` into a stable SARIF rule id, e.g.
+/// `abi.glibc-too-new` for a `GLIBC_2.x` version that exceeds the policy
+/// ceiling, or `abi.forbidden-library` for a disallowed `DT_NEEDED` entry.
+fn sarif_rule_id(violation: &SharedObjectViolation) -> &'static str {
+    match violation.category {
+        ViolationCategory::DisallowedSymbolVersion => {
+            if violation.subject.starts_with("GLIBCXX") {
+                "abi.glibcxx-too-new"
+            } else if violation.subject.starts_with("CXXABI") {
+                "abi.cxxabi-too-new"
+            } else if violation.subject.starts_with("GLIBC") {
+                "abi.glibc-too-new"
+            } else {
+                "abi.symbol-version-too-new"
+            }
+        }
+        ViolationCategory::ForbiddenLibrary => "abi.forbidden-library",
+        ViolationCategory::WrongAbiTag => "abi.wrong-abi-tag",
+        ViolationCategory::CheckError => "abi.check-error",
+    }
+}
+
+fn sarif_result_for_violation(violation: &SharedObjectViolation, level: &str) -> SarifResult {
+    SarifResult {
+        rule_id: sarif_rule_id(violation).to_string(),
+        level: level.to_string(),
+        message: SarifMessage {
+            text: violation.message.clone(),
+        },
+        locations: vec![SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: violation.file.clone(),
+                },
+            },
+        }],
+    }
+}
+
+fn sarif_result_for_missing_variant(repo_id: &str, rule_id: &str, variant: &str) -> SarifResult {
+    SarifResult {
+        rule_id: rule_id.to_string(),
+        level: "error".to_string(),
+        message: SarifMessage {
+            text: format!("{}: missing required build variant {}", repo_id, variant),
+        },
+        locations: vec![SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: repo_id.to_string(),
+                },
+            },
+        }],
+    }
+}
+
+fn build_sarif_log(results: Vec<SarifResult>) -> SarifLog {
+    let rule_ids: std::collections::BTreeSet<String> =
+        results.iter().map(|r| r.rule_id.clone()).collect();
+
+    SarifLog {
+        schema: SARIF_SCHEMA_URI.to_string(),
+        version: "2.1.0".to_string(),
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "kernel-compliance-check".to_string(),
+                    rules: rule_ids.into_iter().map(|id| SarifRule { id }).collect(),
+                },
+            },
+            results,
+        }],
+    }
+}
+
+/// Render a repository's compliance result as a SARIF log: one `result` per ABI
+/// violation (waived violations are carried at `note` level so they're still
+/// visible), plus one per missing required CUDA/ROCm build variant.
+fn render_sarif_report(repo_id: &str, check_result: &RepositoryCheckResult) -> SarifLog {
+    let mut results = Vec::new();
+
+    for variant in &check_result.abi_status.variants {
+        for violation in &variant.violations {
+            results.push(sarif_result_for_violation(violation, "error"));
+        }
+        for violation in &variant.waived {
+            results.push(sarif_result_for_violation(violation, "note"));
+        }
+    }
+
+    for missing in &check_result.build_status.cuda.missing {
+        results.push(sarif_result_for_missing_variant(
+            repo_id,
+            "variants.missing-cuda",
+            missing,
+        ));
+    }
+    if let Some(rocm) = &check_result.build_status.rocm {
+        for missing in &rocm.missing {
+            results.push(sarif_result_for_missing_variant(
+                repo_id,
+                "variants.missing-rocm",
+                missing,
+            ));
+        }
+    }
+
+    build_sarif_log(results)
+}
+
+/// Render a single repository-level error (not found, fetch failure, missing
+/// snapshot/build directory) as a one-result SARIF log.
+fn render_sarif_error_report(repo_id: &str, message: &str) -> SarifLog {
+    build_sarif_log(vec![SarifResult {
+        rule_id: "repository.check-error".to_string(),
+        level: "error".to_string(),
+        message: SarifMessage {
+            text: message.to_string(),
+        },
+        locations: vec![SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: repo_id.to_string(),
+                },
+            },
+        }],
+    }])
+}
+
+/// A machine-readable compliance report for a repository: for every build variant,
+/// the resolved Python ABI tag, the platform tag the binaries actually demand (based
+/// on the maximum symbol versions the ELF auditor discovered), the per-library
+/// maxima, and the specific violations. Stable and diffable so CI can track ABI
+/// drift between revisions.
+#[derive(Serialize)]
+pub struct ComplianceReport {
+    pub repository: String,
+    pub python_abi_version: String,
+    pub variants: Vec<VariantComplianceEntry>,
+}
+
+#[derive(Serialize)]
+pub struct VariantComplianceEntry {
+    pub name: String,
+    pub platform_tag: String,
+    pub library_versions: std::collections::BTreeMap<String, String>,
+    pub violations: Vec<String>,
+}
+
+/// Build a `ComplianceReport` by re-walking the shared objects of each build variant
+/// and computing the highest glibc/musl-derived platform tag each one actually
+/// requires, rather than just reporting the `--manylinux`/`--musllinux` threshold it
+/// was checked against.
+pub fn build_compliance_report(
+    repo_id: &str,
+    snapshot_dir: &Path,
+    platform: &Platform,
+    python_abi_version: &Version,
+) -> Result<ComplianceReport> {
+    let build_dir = snapshot_dir.join("build");
+    let mut variants = Vec::new();
+
+    if build_dir.exists() {
+        let entries = fs::read_dir(&build_dir)
+            .with_context(|| format!("Failed to read build directory: {:?}", build_dir))?;
+
+        for entry in entries {
+            let entry = entry.context("Failed to read directory entry")?;
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            let variant_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+            let so_files = find_shared_objects(&path)
+                .with_context(|| format!("Failed to find shared objects in variant: {}", variant_name))?;
+
+            let mut library_versions: std::collections::BTreeMap<String, Version> =
+                std::collections::BTreeMap::new();
+            let mut violations = Vec::new();
+
+            for so_path in &so_files {
+                let binary_data = fs::read(so_path)
+                    .with_context(|| format!("Failed to read shared object file: {:?}", so_path))?;
+                let file = object::File::parse(&*binary_data).map_err(|e| {
+                    anyhow::anyhow!("Cannot parse object file: {}: {}", so_path.display(), e)
+                })?;
+
+                let maxima = kernel_abi_check::max_library_versions(&binary_data, file.endianness())
+                    .map_err(|e| anyhow::anyhow!("Failed to audit symbol versions: {}", e))?;
+                for (library, version) in maxima {
+                    library_versions
+                        .entry(library)
+                        .and_modify(|existing| {
+                            if version > *existing {
+                                *existing = version;
+                            }
+                        })
+                        .or_insert(version);
+                }
+                // musl carries no versioned symbols, so its version comes from
+                // probing the `PT_INTERP` loader, as `check_musllinux` does.
+                if let Platform::Musllinux(_) = platform {
+                    if let Some(interp) = find_pt_interp(&file).filter(|i| i.contains("ld-musl")) {
+                        let musl = detect_musl_loader_version(&interp)?;
+                        library_versions
+                            .entry("MUSL".to_string())
+                            .and_modify(|existing| {
+                                if musl > *existing {
+                                    *existing = musl;
+                                }
+                            })
+                            .or_insert(musl);
+                    }
+                }
+
+                let (passed, so_violations, _waived) = check_shared_object(
+                    so_path,
+                    platform,
+                    python_abi_version,
+                    &DEFAULT_MACOS_MIN,
+                    true,
+                    &get_cache_dir()?,
+                    false,
+                    &AbiExceptions::default(),
+                )
+                .with_context(|| format!("Failed to check shared object: {:?}", so_path))?;
+                if !passed {
+                    violations.extend(so_violations.into_iter().map(|v| v.message));
+                }
+            }
+
+            let platform_tag = match platform {
+                Platform::Manylinux(_) => library_versions
+                    .get("GLIBC")
+                    .map(|v| format!("manylinux_{}_{}", v.major, v.minor))
+                    .unwrap_or_else(|| "manylinux_unknown".to_string()),
+                Platform::Musllinux(_) => library_versions
+                    .get("MUSL")
+                    .map(|v| format!("musllinux_{}_{}", v.major, v.minor))
+                    .unwrap_or_else(|| "musllinux_unknown".to_string()),
+            };
+
+            variants.push(VariantComplianceEntry {
+                name: variant_name,
+                platform_tag,
+                library_versions: library_versions
+                    .into_iter()
+                    .map(|(k, v)| (k, v.to_string()))
+                    .collect(),
+                violations,
+            });
+        }
+    }
+
+    Ok(ComplianceReport {
+        repository: repo_id.to_string(),
+        python_abi_version: python_abi_version.to_string(),
+        variants,
+    })
+}
+
+pub fn get_cache_dir() -> Result<PathBuf> {
+    let cache_dir = if let Ok(dir) = std::env::var("HF_KERNELS_CACHE") {
+        PathBuf::from(dir)
+    } else {
+        dirs::home_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(".cache/huggingface/hub")
+    };
+
+    if !cache_dir.exists() {
+        fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
+    }
+
+    Ok(cache_dir)
+}
+
+/// Get "org/name" repo ID from filesystem path
+pub fn get_repo_id_from_path(path: &Path) -> Result<String> {
+    // Extract the organization and model name from the path
+    let dir_name = path
+        .file_name()
+        .ok_or_else(|| CompliantError::Other(format!("Invalid path: {:?}", path)))?
+        .to_string_lossy()
+        .to_string();
+
+    // Remove the "models--" prefix if present
+    let dir_name = dir_name
+        .strip_prefix("models--")
+        .unwrap_or(&dir_name)
+        .replace("--", "/");
+
+    Ok(dir_name)
+}
+
+/// Check if repository has build variants
+pub fn has_build_variants(repo_path: &Path) -> Result<bool> {
+    // Look for the snapshot directory
+    let ref_file = repo_path.join("refs/main");
+    if !ref_file.exists() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&ref_file)
+        .with_context(|| format!("Failed to read ref file: {:?}", ref_file))?;
+
+    let hash = content.trim();
+    let snapshot_dir = repo_path.join(format!("snapshots/{}", hash));
+
+    if !snapshot_dir.exists() {
+        return Ok(false);
+    }
+
+    // Check build directory
+    let build_dir = snapshot_dir.join("build");
+    if !build_dir.exists() {
+        return Ok(false);
+    }
+
+    // Check if build directory has any variant subdirectories
+    let entries = fs::read_dir(&build_dir)
+        .with_context(|| format!("Failed to read build directory: {:?}", build_dir))?;
+
+    for entry in entries {
+        let entry = entry.context("Failed to read directory entry")?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            // At least one build variant exists
+            return Ok(true);
+        }
+    }
+
+    // Build directory exists but is empty
+    Ok(false)
+}
+
+pub fn get_repo_path(repo_id: &str, base_dir: &Path) -> PathBuf {
+    let repo = Repo::with_revision(repo_id.to_string(), RepoType::Model, "main".to_string());
+    base_dir.join(repo.folder_name())
+}
+
+pub async fn fetch_repository_async(repo_id: &str, cache_dir: &Path, revision: &str) -> Result<()> {
+    // Hold an exclusive lock for the whole download + ref-write so a concurrent
+    // fetch (or reader, via `lockfile::lock_shared`) can't observe a half-written
+    // snapshot. Locking is blocking I/O, so do it off the async executor.
+    let repo_path = get_repo_path(repo_id, cache_dir);
+    let _lock = tokio::task::spawn_blocking(move || lockfile::lock_exclusive(&repo_path))
+        .await
+        .context("lock task panicked")??;
+
+    let api = ApiBuilder::new()
+        .high()
+        .build()
+        .context("Failed to create HF API client")?;
+
+    let repo = Repo::with_revision(repo_id.to_string(), RepoType::Model, revision.to_string());
 
-    // Create a stream of tasks and process them concurrently with bounded parallelism
+    let api_repo = api.repo(repo);
+    let info = api_repo
+        .info()
+        .await
+        .context(format!("Failed to fetch repo info for {}", repo_id))?;
+
+    let snapshot_dir = repo_path.join("snapshots").join(&info.sha);
+
+    // Downloads go straight to their final snapshot location via resumable,
+    // range-based chunks instead of hf-hub's whole-file `ApiRepo::download`, so
+    // an interrupted multi-gigabyte `.so` resumes instead of restarting.
     use futures::stream::{self, StreamExt};
 
-    let download_results = stream::iter(file_names)
-        .map(|file_name| {
-            // Create a new API instance for each download to avoid shared state issues
-            let api = ApiBuilder::new().high().build().unwrap();
-            let repo_clone =
-                Repo::with_revision(repo_id.to_string(), RepoType::Model, revision.to_string());
-            let download_repo = api.repo(repo_clone);
-            let file_to_download = file_name.clone();
+    let client = reqwest::Client::new();
+    let download_results = stream::iter(info.siblings.iter().cloned())
+        .map(|sibling| {
+            let client = client.clone();
+            let url = api_repo.url(&sibling.rfilename);
+            let dest = snapshot_dir.join(&sibling.rfilename);
+            let expected = integrity::expected_digest(&sibling);
+            let file_name = sibling.rfilename.clone();
 
             async move {
-                if let Err(e) = download_repo.download(&file_name).await {
+                match download::download_resumable(&client, &url, &dest, expected.as_ref()).await {
+                    Ok(()) => Ok(file_name),
                     // Special case for __init__.py which can be empty
-                    if file_name.contains("__init__.py") && matches!(e, ApiError::RequestError(_)) {
-                        return Ok(file_name);
-                    }
-
-                    Err(anyhow::anyhow!("Failed to download {}: {}", file_name, e))
-                } else {
-                    Ok(file_to_download)
+                    Err(_) if file_name.contains("__init__.py") => Ok(file_name),
+                    Err(e) => Err(anyhow::anyhow!("Failed to download {}: {}", file_name, e)),
                 }
             }
         })
@@ -568,6 +1469,14 @@ pub async fn fetch_repository_async(repo_id: &str, revision: &str) -> Result<()>
         }
     }
 
+    let ref_path = repo_path.join("refs").join(revision);
+    if let Some(parent) = ref_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create refs directory: {:?}", parent))?;
+    }
+    fs::write(&ref_path, &info.sha)
+        .with_context(|| format!("Failed to write ref file: {:?}", ref_path))?;
+
     // Log success info
     println!(
         "Downloaded {} files successfully ({} failed)",
@@ -578,12 +1487,140 @@ pub async fn fetch_repository_async(repo_id: &str, revision: &str) -> Result<()>
 }
 
 /// Synchronous wrapper for the async fetch repository function
-pub fn fetch_repository(repo_id: &str, _cache_dir: &Path, revision: &str) -> Result<()> {
+pub fn fetch_repository(repo_id: &str, cache_dir: &Path, revision: &str) -> Result<()> {
     println!("fetching: {} (revision: {})", repo_id, revision);
 
     let rt = tokio::runtime::Runtime::new().context("Failed to create Tokio runtime")?;
 
-    rt.block_on(fetch_repository_async(repo_id, revision))
+    rt.block_on(fetch_repository_async(repo_id, cache_dir, revision))
+}
+
+/// Whether `revision` is a pinned 40-character commit hash rather than a named ref
+/// like `main`. A pinned revision resolves straight to `snapshots/<revision>`; a
+/// named ref first has to be looked up in `refs/<revision>`.
+fn is_commit_hash(revision: &str) -> bool {
+    revision.len() == 40 && revision.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Fetch only the `build/` tree of a repository revision (plus a root-level
+/// `compliance.toml`, if present): the shared objects a compliance check actually
+/// inspects, not the full snapshot `resolve_snapshot_dir`'s caller would otherwise
+/// need to download first. Used to resolve a revision on demand, so CI can point
+/// the tool at a bare repo id without a prior manual clone.
+async fn fetch_build_tree_async(repo_id: &str, cache_dir: &Path, revision: &str) -> Result<String> {
+    // Hold an exclusive lock for the whole download + ref-write so a concurrent
+    // fetch (or reader, via `lockfile::lock_shared`) can't observe a half-written
+    // snapshot. Locking is blocking I/O, so do it off the async executor.
+    let repo_path = get_repo_path(repo_id, cache_dir);
+    let _lock = tokio::task::spawn_blocking({
+        let repo_path = repo_path.clone();
+        move || lockfile::lock_exclusive(&repo_path)
+    })
+    .await
+    .context("lock task panicked")??;
+
+    let api = ApiBuilder::new()
+        .high()
+        .build()
+        .context("Failed to create HF API client")?;
+
+    let repo = Repo::with_revision(repo_id.to_string(), RepoType::Model, revision.to_string());
+    let api_repo = api.repo(repo);
+    let info = api_repo
+        .info()
+        .await
+        .context(format!("Failed to fetch repo info for {}", repo_id))?;
+
+    let snapshot_dir = repo_path.join("snapshots").join(&info.sha);
+
+    // Also pull down a root-level `compliance.toml`, if the repo has one: it's
+    // consulted by `CompliancePolicy::resolve` the same as it is for repos fetched
+    // via `fetch_repository_async`, so skipping it here would make `diff --auto-fetch`
+    // silently ignore a repo's policy manifest whenever the repo isn't already cached.
+    let build_siblings: Vec<_> = info
+        .siblings
+        .iter()
+        .filter(|sibling| sibling.rfilename.starts_with("build/") || sibling.rfilename == "compliance.toml")
+        .cloned()
+        .collect();
+
+    let build_file_count = build_siblings.len();
+
+    use futures::stream::{self, StreamExt};
+
+    let client = reqwest::Client::new();
+    let download_results = stream::iter(build_siblings)
+        .map(|sibling| {
+            let client = client.clone();
+            let url = api_repo.url(&sibling.rfilename);
+            let dest = snapshot_dir.join(&sibling.rfilename);
+            let expected = integrity::expected_digest(&sibling);
+            let file_name = sibling.rfilename.clone();
+
+            async move {
+                match download::download_resumable(&client, &url, &dest, expected.as_ref()).await {
+                    Ok(()) => Ok(file_name),
+                    Err(e) => Err(anyhow::anyhow!("Failed to download {}: {}", file_name, e)),
+                }
+            }
+        })
+        .buffer_unordered(10)
+        .collect::<Vec<_>>()
+        .await;
+
+    let failed: Vec<String> = download_results
+        .into_iter()
+        .filter_map(|result| result.err().map(|e| e.to_string()))
+        .collect();
+
+    if !failed.is_empty() {
+        for error in &failed {
+            eprintln!("{}", error);
+        }
+        return Err(CompliantError::FetchError(format!(
+            "Failed to download {} file(s) of the build/ tree for {}",
+            failed.len(),
+            repo_id
+        ))
+        .into());
+    }
+
+    let ref_path = repo_path.join("refs").join(revision);
+    if let Some(parent) = ref_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create refs directory: {:?}", parent))?;
+    }
+    fs::write(&ref_path, &info.sha)
+        .with_context(|| format!("Failed to write ref file: {:?}", ref_path))?;
+
+    // A partially-populated cache shouldn't be able to masquerade as complete: read
+    // the ref back and check it against what the Hub just reported, rather than
+    // trusting the write we issued a moment ago actually landed.
+    let written_hash = fs::read_to_string(&ref_path)
+        .with_context(|| format!("Failed to read back ref file: {:?}", ref_path))?;
+    if written_hash.trim() != info.sha {
+        return Err(CompliantError::FetchError(format!(
+            "ref file {:?} does not match the revision the Hub reported ({})",
+            ref_path, info.sha
+        ))
+        .into());
+    }
+
+    println!(
+        "Downloaded build/ tree for {} at {} ({} files)",
+        repo_id, revision, build_file_count
+    );
+
+    Ok(info.sha)
+}
+
+/// Synchronous wrapper for `fetch_build_tree_async`. Returns the commit hash the
+/// revision resolved to.
+fn fetch_build_tree(repo_id: &str, cache_dir: &Path, revision: &str) -> Result<String> {
+    println!("fetching build/ tree: {} (revision: {})", repo_id, revision);
+
+    let rt = tokio::runtime::Runtime::new().context("Failed to create Tokio runtime")?;
+    rt.block_on(fetch_build_tree_async(repo_id, cache_dir, revision))
 }
 
 pub fn get_build_variants(repo_path: &Path) -> Result<Vec<Variant>> {
@@ -653,27 +1690,287 @@ pub fn get_build_status_summary(
     }
 }
 
+/// The kind of ABI problem a `SharedObjectViolation` represents, so downstream
+/// automation can filter/aggregate without re-parsing `message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationCategory {
+    /// A required symbol version (e.g. `GLIBC_2.34` or a `PYTHON_x.y` stable-ABI
+    /// version) exceeds what the manylinux policy or Python ABI allows.
+    DisallowedSymbolVersion,
+    /// A linked library, framework, or DLL falls outside the allowed baseline for
+    /// the binary's target platform.
+    ForbiddenLibrary,
+    /// The binary's declared ABI tag (musl version, macOS minimum OS, PE subsystem)
+    /// doesn't match what was requested.
+    WrongAbiTag,
+    /// The shared object itself couldn't be read or parsed, so no ABI category
+    /// applies.
+    CheckError,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SharedObjectViolation {
+    /// The shared object this violation was found in.
+    pub file: String,
+    pub category: ViolationCategory,
+    /// The symbol, library, or tag name the violation is about (e.g. `GLIBC_2.34`,
+    /// `libfoo.so.1`, `macos-min`).
+    pub subject: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<String>,
+    /// Human-readable rendering, used by the console formatter and as a fallback
+    /// for categories that don't carry a required/allowed version.
     pub message: String,
-    // TODO: Explore what other fields we may need
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VariantResult {
-    pub name: String,
-    pub is_compatible: bool,
-    pub violations: Vec<SharedObjectViolation>,
-    pub has_shared_objects: bool,
-}
+impl SharedObjectViolation {
+    fn symbol_version(so_path: &Path, violation: &kernel_abi_check::Violation) -> Self {
+        SharedObjectViolation {
+            file: so_path.display().to_string(),
+            category: ViolationCategory::DisallowedSymbolVersion,
+            subject: violation.symbol.clone(),
+            required: Some(violation.required.to_string()),
+            allowed: Some(violation.allowed.to_string()),
+            message: format!(
+                "{}: requires {}, which exceeds the allowed {} {}",
+                so_path.display(),
+                violation.symbol,
+                violation.library,
+                violation.allowed
+            ),
+        }
+    }
 
-#[derive(Debug, Clone)]
-pub struct AbiCheckResult {
-    pub overall_compatible: bool,
-    pub variants: Vec<VariantResult>,
-    pub manylinux_version: String,
-    pub python_abi_version: Version,
-}
+    /// Classify a pre-rendered violation string from `check_musllinux`,
+    /// `check_macos_min`, or `check_pe_baseline` by the stable prefix each of those
+    /// always uses, rather than widening their return type across crates.
+    fn from_target_text(so_path: &Path, text: &str) -> Self {
+        let file = so_path.display().to_string();
+
+        if let Some(library) = text
+            .strip_prefix("links against ")
+            .or_else(|| text.strip_prefix("imports "))
+            .and_then(|rest| rest.split(" which is outside").next())
+        {
+            return SharedObjectViolation {
+                file,
+                category: ViolationCategory::ForbiddenLibrary,
+                subject: library.to_string(),
+                required: None,
+                allowed: None,
+                message: format!("{}: {}", so_path.display(), text),
+            };
+        }
+
+        let subject = if text.starts_with("declares minimum OS version") {
+            "macos-min"
+        } else if text.starts_with("unexpected PE subsystem") {
+            "subsystem"
+        } else {
+            "musl"
+        };
+
+        SharedObjectViolation {
+            file,
+            category: ViolationCategory::WrongAbiTag,
+            subject: subject.to_string(),
+            required: None,
+            allowed: None,
+            message: format!("{}: {}", so_path.display(), text),
+        }
+    }
+
+    fn check_error(so_path: &Path, error: &anyhow::Error) -> Self {
+        SharedObjectViolation {
+            file: so_path.display().to_string(),
+            category: ViolationCategory::CheckError,
+            subject: "check_error".to_string(),
+            required: None,
+            allowed: None,
+            message: format!("Failed to check shared object {:?}: {}", so_path, error),
+        }
+    }
+}
+
+/// Symbol and library names a compliance policy has pre-approved, so a kernel
+/// that legitimately links a vendored library doesn't trip on it every run.
+/// Matched as simple `*`-wildcard globs rather than full regexes, since the
+/// subjects being matched (`GLIBC_2.34`, `libfoo.so.1`) never need more than a
+/// trailing-version wildcard.
+#[derive(Debug, Clone, Default)]
+pub struct AbiExceptions {
+    /// Allowed symbol names, e.g. `GLIBC_2.34` or `CXXABI_*`. Matched against a
+    /// `DisallowedSymbolVersion` violation's `subject`.
+    pub allowed_symbols: Vec<String>,
+    /// Allowed `DT_NEEDED` library names, e.g. `libfoo.so.1` or `libfoo.so.*`.
+    /// Matched against a `ForbiddenLibrary` violation's `subject`.
+    pub allowed_libraries: Vec<String>,
+}
+
+impl AbiExceptions {
+    fn is_waived(&self, violation: &SharedObjectViolation) -> bool {
+        let patterns: &[String] = match violation.category {
+            ViolationCategory::DisallowedSymbolVersion => &self.allowed_symbols,
+            ViolationCategory::ForbiddenLibrary => &self.allowed_libraries,
+            ViolationCategory::WrongAbiTag | ViolationCategory::CheckError => return false,
+        };
+        patterns.iter().any(|pattern| glob_match(pattern, &violation.subject))
+    }
+}
+
+/// Match `text` against a glob `pattern` whose only special character is `*`
+/// (matches any run of characters, including none). Good enough for the
+/// version-suffix wildcards an allowlist needs without pulling in a full glob crate.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn inner(pattern: &[u8], text: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => text.is_empty(),
+            Some((b'*', rest)) => {
+                inner(rest, text) || (!text.is_empty() && inner(pattern, &text[1..]))
+            }
+            Some((&p, rest)) => text.first() == Some(&p) && inner(rest, &text[1..]),
+        }
+    }
+    inner(pattern.as_bytes(), text.as_bytes())
+}
+
+/// Which libc-based platform policy a `Check` run is auditing against.
+#[derive(Debug, Clone)]
+pub enum Platform {
+    Manylinux(String),
+    Musllinux(Version),
+}
+
+impl Platform {
+    /// The tag shown in reports, e.g. `manylinux_2_28` or `musllinux_1_2`.
+    pub fn tag(&self) -> String {
+        match self {
+            Platform::Manylinux(version) => version.clone(),
+            Platform::Musllinux(version) => format!("musllinux_{}_{}", version.major, version.minor),
+        }
+    }
+}
+
+/// Locate the `PT_INTERP` dynamic loader path embedded in an ELF shared object, if any.
+///
+/// `PT_INTERP` is a program header, but ELF segments carry no name field (unlike
+/// Mach-O) so `ObjectSegment::name()` never returns one; the interpreter path is
+/// instead duplicated into the `.interp` *section*, which is what we read here.
+fn find_pt_interp(file: &object::File) -> Option<String> {
+    let section = file.section_by_name(".interp")?;
+    let data = section.data().ok()?;
+    let data = data.split(|&b| b == 0).next().unwrap_or(data);
+    Some(String::from_utf8_lossy(data).to_string())
+}
+
+/// Directories a musl dynamic loader may legitimately live in. `detect_musl_loader_version`
+/// refuses to execute a `PT_INTERP` path outside of these, since that path is read directly
+/// out of attacker-controlled binary content.
+const TRUSTED_MUSL_LOADER_DIRS: &[&str] = &["/lib", "/usr/lib", "/lib64", "/usr/lib64"];
+
+/// Run the musl dynamic loader found at `interp_path` with no arguments and parse the
+/// `Version x.y` banner it prints to stderr, mirroring how `packaging` probes musl's
+/// own version to derive a `musllinux_x_y` platform tag.
+///
+/// `interp_path` comes straight out of the audited binary's `.interp` section, so before
+/// executing anything we require it to resolve to one of `TRUSTED_MUSL_LOADER_DIRS` on the
+/// *local* filesystem. This stops a crafted shared object from naming an arbitrary local
+/// program as its "musl loader" and having `compliant` execute it.
+fn detect_musl_loader_version(interp_path: &str) -> Result<Version> {
+    let canonical = std::fs::canonicalize(interp_path)
+        .with_context(|| format!("musl loader path does not exist: {}", interp_path))?;
+    let is_trusted = TRUSTED_MUSL_LOADER_DIRS
+        .iter()
+        .any(|dir| canonical.starts_with(dir));
+    if !is_trusted {
+        return Err(CompliantError::MusllinuxCheckError(format!(
+            "Refusing to execute untrusted PT_INTERP path: {} (resolved to {})",
+            interp_path,
+            canonical.display()
+        ))
+        .into());
+    }
+
+    let output = Command::new(&canonical)
+        .output()
+        .with_context(|| format!("Failed to execute musl loader: {}", interp_path))?;
+
+    let banner = String::from_utf8_lossy(&output.stderr);
+    let re = Regex::new(r"Version (\d+)\.(\d+)").context("Invalid musl version regex")?;
+    let captures = re
+        .captures(&banner)
+        .ok_or_else(|| CompliantError::MusllinuxCheckError(format!(
+            "Could not parse musl version from loader banner: {:?}",
+            banner
+        )))?;
+
+    let major: usize = captures[1].parse().context("Invalid musl major version")?;
+    let minor: usize = captures[2].parse().context("Invalid musl minor version")?;
+
+    Ok(Version {
+        major,
+        minor,
+        patch: 0,
+    })
+}
+
+/// Determine the musllinux platform tag a shared object actually requires by locating
+/// its `PT_INTERP` musl loader and probing it for its version.
+pub fn detect_musllinux_requirement(file: &object::File, arch: &str) -> Result<Option<String>> {
+    let interp = match find_pt_interp(file) {
+        Some(interp) => interp,
+        None => return Ok(None),
+    };
+
+    if !interp.contains("ld-musl") {
+        return Ok(None);
+    }
+
+    let version = detect_musl_loader_version(&interp)?;
+    Ok(Some(format!(
+        "musllinux_{}_{}_{}",
+        version.major, version.minor, arch
+    )))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantResult {
+    pub name: String,
+    pub is_compatible: bool,
+    pub violations: Vec<SharedObjectViolation>,
+    /// Violations that matched an `AbiExceptions` entry and so were subtracted
+    /// from `violations` before `is_compatible` was computed.
+    pub waived: Vec<SharedObjectViolation>,
+    pub has_shared_objects: bool,
+    /// The target platform detected from this variant's shared objects (`linux`,
+    /// `macos`, `windows`), or `none` when it ships no shared objects at all.
+    pub platform: String,
+    /// The highest `GLIBC_x.y` any shared object in this variant actually imports,
+    /// regardless of whether it exceeds the policy ceiling. `None` when the
+    /// variant links no versioned glibc symbols (e.g. a macOS/Windows variant).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_glibc: Option<String>,
+    /// The highest `GLIBCXX_3.4.y` required. `None` when no shared object here
+    /// links libstdc++.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_glibcxx: Option<String>,
+    /// The highest `CXXABI_1.3.z` required. `None` when no shared object here
+    /// links libstdc++.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cxxabi: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AbiCheckResult {
+    pub overall_compatible: bool,
+    pub variants: Vec<VariantResult>,
+    pub manylinux_version: String,
+    pub python_abi_version: Version,
+    pub macos_min_version: Version,
+}
 
 impl Serialize for AbiCheckResult {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -681,15 +1978,42 @@ impl Serialize for AbiCheckResult {
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("AbiCheckResult", 4)?;
+        let mut state = serializer.serialize_struct("AbiCheckResult", 5)?;
         state.serialize_field("overall_compatible", &self.overall_compatible)?;
         state.serialize_field("variants", &self.variants)?;
         state.serialize_field("manylinux_version", &self.manylinux_version)?;
         state.serialize_field("python_abi_version", &self.python_abi_version.to_string())?;
+        state.serialize_field("macos_min_version", &self.macos_min_version.to_string())?;
         state.end()
     }
 }
 
+/// Minimum macOS version assumed when a caller doesn't have a `--macos-min` of
+/// its own to thread through (e.g. the SBOM builder, which audits every variant
+/// regardless of platform).
+pub(crate) const DEFAULT_MACOS_MIN: Version = Version {
+    major: 11,
+    minor: 0,
+    patch: 0,
+};
+
+/// Extensions of the compiled-extension artifacts a kernel variant can ship,
+/// across every target platform the build matrix produces: ELF `.so` on Linux,
+/// Mach-O `.dylib` on macOS, and PE `.dll`/`.pyd` on Windows.
+const SHARED_OBJECT_EXTENSIONS: &[&str] = &["so", "dylib", "dll", "pyd"];
+
+/// The target platform a shared object's extension implies, for reporting purposes.
+/// Parsing the file itself would be more precise, but the build matrix never mixes
+/// extensions across platforms, so the extension alone is a reliable label.
+fn target_platform_label(so_path: &Path) -> &'static str {
+    match so_path.extension().and_then(|ext| ext.to_str()) {
+        Some("so") => "linux",
+        Some("dylib") => "macos",
+        Some("dll") | Some("pyd") => "windows",
+        _ => "unknown",
+    }
+}
+
 pub fn find_shared_objects(dir: &Path) -> Result<Vec<PathBuf>> {
     let mut so_files = Vec::new();
 
@@ -706,10 +2030,10 @@ pub fn find_shared_objects(dir: &Path) -> Result<Vec<PathBuf>> {
 
         if path.is_dir() {
             let mut subdir_so_files = find_shared_objects(&path)
-                .with_context(|| format!("Failed to find .so files in subdirectory: {:?}", path))?;
+                .with_context(|| format!("Failed to find shared objects in subdirectory: {:?}", path))?;
             so_files.append(&mut subdir_so_files);
-        } else if let Some(extension) = path.extension() {
-            if extension == "so" {
+        } else if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
+            if SHARED_OBJECT_EXTENSIONS.contains(&extension) {
                 so_files.push(path);
             }
         }
@@ -718,63 +2042,322 @@ pub fn find_shared_objects(dir: &Path) -> Result<Vec<PathBuf>> {
     Ok(so_files)
 }
 
+/// Key identifying a cached ABI-check verdict: the pass/fail result for a shared
+/// object depends on the binary's own bytes, the platform/Python ABI/macOS
+/// minimum it's being checked against, *and* which violations a policy has
+/// pre-approved, so all five go into the cache key.
+struct AbiCheckCacheKey {
+    digest: String,
+    platform_tag: String,
+    python_abi_version: String,
+    macos_min_version: String,
+    exceptions_digest: String,
+}
+
+/// Always holds the *full* violation/waived lists, regardless of whether the run
+/// that populated the cache passed `--show-violations`: the key carries no record
+/// of that flag, so a cache entry must work for either a `--show-violations` or
+/// a plain lookup. `check_shared_object` applies the `show_violations` filter
+/// itself after reading (or writing) this.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedCheckResult {
+    passed: bool,
+    violations: Vec<SharedObjectViolation>,
+    waived: Vec<SharedObjectViolation>,
+}
+
+fn abi_check_cache_path(cache_dir: &Path, key: &AbiCheckCacheKey) -> PathBuf {
+    cache_dir.join("abi-check-cache").join(format!(
+        "{}-{}-{}-{}-{}.json",
+        key.digest,
+        key.platform_tag,
+        key.python_abi_version,
+        key.macos_min_version,
+        key.exceptions_digest
+    ))
+}
+
+fn read_abi_check_cache(
+    cache_dir: &Path,
+    key: &AbiCheckCacheKey,
+) -> Result<Option<CachedCheckResult>> {
+    let path = abi_check_cache_path(cache_dir, key);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let data = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read ABI check cache entry: {:?}", path))?;
+    let cached = serde_json::from_str(&data)
+        .with_context(|| format!("Failed to parse ABI check cache entry: {:?}", path))?;
+
+    Ok(Some(cached))
+}
+
+fn write_abi_check_cache(
+    cache_dir: &Path,
+    key: &AbiCheckCacheKey,
+    result: &CachedCheckResult,
+) -> Result<()> {
+    let path = abi_check_cache_path(cache_dir, key);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create ABI check cache directory: {:?}", parent))?;
+    }
+
+    let data =
+        serde_json::to_string(result).context("Failed to serialize ABI check cache entry")?;
+    fs::write(&path, data).with_context(|| format!("Failed to write ABI check cache entry: {:?}", path))?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn check_shared_object(
     so_path: &Path,
-    manylinux_version: &str,
+    platform: &Platform,
     python_abi_version: &Version,
+    macos_min: &Version,
     show_violations: bool,
-) -> Result<(bool, String)> {
-    let mut violations_output = String::new();
-
-    // Read binary data
-    let binary_data = fs::read(so_path)
-        .with_context(|| format!("Failed to read shared object file: {:?}", so_path))?;
+    cache_dir: &Path,
+    no_cache: bool,
+    exceptions: &AbiExceptions,
+) -> Result<(bool, Vec<SharedObjectViolation>, Vec<SharedObjectViolation>)> {
+    // Memory-map the shared object instead of copying it onto the heap; kernel
+    // variants can ship multi-hundred-MB .so files and we only ever read them.
+    let so_file = fs::File::open(so_path)
+        .with_context(|| format!("Failed to open shared object file: {:?}", so_path))?;
+    let mmap = unsafe { Mmap::map(&so_file) }
+        .with_context(|| format!("Failed to memory-map shared object file: {:?}", so_path))?;
+    let binary_data: &[u8] = &mmap;
+
+    // The pass/fail verdict for a binary's bytes is stable for a given platform,
+    // Python ABI target, macOS target, and set of policy exceptions, so look it
+    // up before paying for parsing.
+    let exceptions_digest = blake3::hash(
+        format!("{:?}|{:?}", exceptions.allowed_symbols, exceptions.allowed_libraries).as_bytes(),
+    )
+    .to_hex()
+    .to_string();
+    let cache_key = (!no_cache).then(|| AbiCheckCacheKey {
+        digest: blake3::hash(binary_data).to_hex().to_string(),
+        platform_tag: platform.tag(),
+        python_abi_version: python_abi_version.to_string(),
+        macos_min_version: macos_min.to_string(),
+        exceptions_digest,
+    });
+
+    if let Some(key) = &cache_key {
+        if let Some(cached) = read_abi_check_cache(cache_dir, key)? {
+            let (violations, waived) = if show_violations {
+                (cached.violations, cached.waived)
+            } else {
+                (Vec::new(), Vec::new())
+            };
+            return Ok((cached.passed, violations, waived));
+        }
+    }
 
     // Parse object file
-    let file = object::File::parse(&*binary_data)
+    let file = object::File::parse(binary_data)
         .map_err(|e| anyhow::anyhow!("Cannot parse object file: {}: {}", so_path.display(), e))?;
 
-    // Run manylinux check
-    let manylinux_result = check_manylinux(
-        manylinux_version,
-        file.architecture(),
-        file.endianness(),
-        file.symbols(),
-    )
-    .map_err(|e| anyhow::anyhow!("Manylinux check error: {}", e))?;
+    // Dispatch on the binary's own format rather than the requested `--manylinux`/
+    // `--musllinux` platform: a kernel repo's build matrix ships Linux, macOS, and
+    // Windows variants side by side, and each target is audited against its own
+    // rules (glibc/musl symbol versions, Mach-O minimum-OS + linked frameworks, or
+    // PE subsystem + imported DLLs).
+    // The bool half of each arm's result is unused: whether a violation actually
+    // fails the check now depends on `exceptions`, computed below.
+    let (_, target_violations): (bool, Vec<SharedObjectViolation>) = match file.format() {
+        object::BinaryFormat::Elf => match platform {
+            Platform::Manylinux(manylinux_version) => {
+                let result = check_manylinux(manylinux_version, binary_data, file.endianness())
+                    .map_err(|e| anyhow::anyhow!("Manylinux check error: {}", e))?;
+                let violations = result
+                    .iter()
+                    .map(|v| SharedObjectViolation::symbol_version(so_path, v))
+                    .collect::<Vec<_>>();
+                (result.is_empty(), violations)
+            }
+            Platform::Musllinux(threshold) => {
+                let result = check_musllinux(so_path, &file, threshold)?;
+                let violations = result
+                    .iter()
+                    .map(|text| SharedObjectViolation::from_target_text(so_path, text))
+                    .collect::<Vec<_>>();
+                (result.is_empty(), violations)
+            }
+        },
+        object::BinaryFormat::MachO => {
+            let result = kernel_abi_check::check_macos_min(macos_min, &file)
+                .map_err(|e| anyhow::anyhow!("macOS check error: {}", e))?;
+            let violations = result
+                .iter()
+                .map(|text| SharedObjectViolation::from_target_text(so_path, text))
+                .collect::<Vec<_>>();
+            (result.is_empty(), violations)
+        }
+        object::BinaryFormat::Pe => {
+            let result = kernel_abi_check::check_pe_baseline(&file)
+                .map_err(|e| anyhow::anyhow!("PE check error: {}", e))?;
+            let violations = result
+                .iter()
+                .map(|text| SharedObjectViolation::from_target_text(so_path, text))
+                .collect::<Vec<_>>();
+            (result.is_empty(), violations)
+        }
+        _ => (true, Vec::new()),
+    };
+
+    // Python-ABI stable-ABI symbol versioning is a Linux/ELF-wheel convention; it
+    // doesn't apply to Mach-O or PE variants.
+    let python_abi_result = if file.format() == object::BinaryFormat::Elf {
+        check_python_abi(python_abi_version, binary_data, file.endianness())
+            .map_err(|e| anyhow::anyhow!("Python ABI check error: {}", e))?
+    } else {
+        Vec::new()
+    };
+
+    let all_violations: Vec<SharedObjectViolation> = target_violations
+        .into_iter()
+        .chain(
+            python_abi_result
+                .iter()
+                .map(|v| SharedObjectViolation::symbol_version(so_path, v)),
+        )
+        .collect();
 
-    // Run Python ABI check
-    let python_abi_result = check_python_abi(python_abi_version, file.symbols())
-        .map_err(|e| anyhow::anyhow!("Python ABI check error: {}", e))?;
+    let (waived, real_violations): (Vec<_>, Vec<_>) = all_violations
+        .into_iter()
+        .partition(|v| exceptions.is_waived(v));
+
+    let passed = real_violations.is_empty();
+
+    // Cache the full lists regardless of `show_violations` so a later lookup under
+    // a different `show_violations` setting (same binary/platform/exceptions) still
+    // has the real data to filter, rather than replaying whatever this run chose
+    // to display.
+    if let Some(key) = &cache_key {
+        write_abi_check_cache(
+            cache_dir,
+            key,
+            &CachedCheckResult {
+                passed,
+                violations: real_violations.clone(),
+                waived: waived.clone(),
+            },
+        )?;
+    }
 
-    // Determine if checks passed
-    let passed = manylinux_result.is_empty() && python_abi_result.is_empty();
+    // Only surface the detail lists when asked; the pass/fail verdict above
+    // always accounts for waivers regardless of `show_violations`.
+    let (violations, waived) = if show_violations {
+        (real_violations, waived)
+    } else {
+        (Vec::new(), Vec::new())
+    };
 
-    // Generate violations output if requested
-    if !passed && show_violations {
-        if !manylinux_result.is_empty() {
-            violations_output.push_str("\n  manylinux violations:\n");
-            for violation in &manylinux_result {
-                violations_output.push_str(&format!("    - {:?}\n", violation));
-            }
+    Ok((passed, violations, waived))
+}
+
+/// Walk every shared object in a variant and return the maximum version required
+/// of each versioned library (`GLIBC`, `GLIBCXX`, `CXXABI`, ...) it actually
+/// imports, independent of whether that exceeds any policy ceiling. Used to
+/// report *how far* over the line a failing variant is.
+fn variant_max_library_versions(so_files: &[PathBuf]) -> Result<std::collections::HashMap<String, Version>> {
+    let mut maxima: std::collections::HashMap<String, Version> = std::collections::HashMap::new();
+
+    for so_path in so_files {
+        let so_file = fs::File::open(so_path)
+            .with_context(|| format!("Failed to open shared object file: {:?}", so_path))?;
+        let mmap = unsafe { Mmap::map(&so_file) }
+            .with_context(|| format!("Failed to memory-map shared object file: {:?}", so_path))?;
+        let binary_data: &[u8] = &mmap;
+        let file = object::File::parse(binary_data)
+            .map_err(|e| anyhow::anyhow!("Cannot parse object file: {}: {}", so_path.display(), e))?;
+
+        // Symbol-version auditing is an ELF/glibc convention; macOS and Windows
+        // variants have no GLIBC/GLIBCXX/CXXABI requirements to report.
+        if file.format() != object::BinaryFormat::Elf {
+            continue;
+        }
+
+        let file_maxima = kernel_abi_check::max_library_versions(binary_data, file.endianness())
+            .map_err(|e| anyhow::anyhow!("Failed to audit symbol versions: {}", e))?;
+        for (library, version) in file_maxima {
+            maxima
+                .entry(library)
+                .and_modify(|existing| {
+                    if version > *existing {
+                        *existing = version;
+                    }
+                })
+                .or_insert(version);
         }
+    }
+
+    Ok(maxima)
+}
 
-        if !python_abi_result.is_empty() {
-            violations_output.push_str("\n  python abi violations:\n");
-            for violation in &python_abi_result {
-                violations_output.push_str(&format!("    - {:?}\n", violation));
+/// Check a shared object's musl loader requirement against a `--musllinux` threshold.
+fn check_musllinux(
+    so_path: &Path,
+    file: &object::File,
+    threshold: &Version,
+) -> Result<Vec<String>> {
+    let arch = format!("{:?}", file.architecture()).to_lowercase();
+    match detect_musllinux_requirement(file, &arch)? {
+        Some(_tag) => {
+            let required = detect_musl_loader_version(
+                &find_pt_interp(file)
+                    .ok_or_else(|| CompliantError::MusllinuxCheckError(format!(
+                        "No PT_INTERP found while re-checking musl version for {:?}",
+                        so_path
+                    )))?,
+            )?;
+
+            if required > *threshold {
+                Ok(vec![format!(
+                    "requires musl {} which exceeds musllinux_{}_{} threshold",
+                    required, threshold.major, threshold.minor
+                )])
+            } else {
+                Ok(Vec::new())
             }
         }
+        None => Ok(Vec::new()),
     }
+}
 
-    Ok((passed, violations_output))
+/// Run `f` on rayon's global thread pool, or on a freshly built pool capped at
+/// `jobs` worker threads when the caller passed an explicit `--jobs`/`-j` limit.
+/// `None` (the default) leaves rayon's own default in place, which is already
+/// the available parallelism.
+fn run_with_job_limit<T: Send>(jobs: Option<usize>, f: impl FnOnce() -> T + Send) -> Result<T> {
+    match jobs {
+        Some(n) if n > 0 => {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .context("Failed to build worker thread pool")?;
+            Ok(pool.install(f))
+        }
+        _ => Ok(f()),
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn check_abi_for_repository(
     snapshot_dir: &Path,
-    manylinux_version: &str,
+    platform: &Platform,
     python_abi_version: &Version,
+    macos_min: &Version,
     show_violations: bool,
+    cache_dir: &Path,
+    no_cache: bool,
+    exceptions: &AbiExceptions,
+    jobs: Option<usize>,
 ) -> Result<AbiCheckResult> {
     let build_dir = snapshot_dir.join("build");
 
@@ -783,8 +2366,9 @@ pub fn check_abi_for_repository(
         return Ok(AbiCheckResult {
             overall_compatible: false,
             variants: Vec::new(),
-            manylinux_version: manylinux_version.to_string(),
+            manylinux_version: platform.tag(),
             python_abi_version: python_abi_version.clone(),
+            macos_min_version: macos_min.clone(),
         });
     }
 
@@ -811,67 +2395,123 @@ pub fn check_abi_for_repository(
         return Ok(AbiCheckResult {
             overall_compatible: false,
             variants: Vec::new(),
-            manylinux_version: manylinux_version.to_string(),
+            manylinux_version: platform.tag(),
             python_abi_version: python_abi_version.clone(),
+            macos_min_version: macos_min.clone(),
         });
     }
 
-    let mut variant_results = Vec::new();
-
-    // Check each variant
-    for variant_path in variant_paths.iter() {
-        let variant_name = variant_path
-            .file_name()
-            .ok_or_else(|| {
-                CompliantError::Other(format!("Invalid variant path: {:?}", variant_path))
-            })?
-            .to_string_lossy()
-            .to_string();
-
-        let so_files = find_shared_objects(variant_path).with_context(|| {
-            format!("Failed to find shared objects in variant: {}", variant_name)
-        })?;
-
-        let has_shared_objects = !so_files.is_empty();
-
-        // If no shared objects, mark as compatible and continue
-        if !has_shared_objects {
-            variant_results.push(VariantResult {
-                name: variant_name,
-                is_compatible: true,
-                violations: Vec::new(),
-                has_shared_objects: false,
-            });
-            continue;
-        }
-
-        let mut variant_violations = Vec::new();
-
-        // Check each shared object in the variant
-        for so_path in &so_files {
-            let (passed, violations_text) = check_shared_object(
-                so_path,
-                manylinux_version,
-                python_abi_version,
-                show_violations,
-            )
-            .with_context(|| format!("Failed to check shared object: {:?}", so_path))?;
+    // Variants run on a bounded rayon thread pool (capped at `jobs` workers when
+    // given, otherwise rayon's own default); a failure scanning one variant's
+    // shared objects is captured as a violation on that variant rather than aborting
+    // the checks already in flight for the others.
+    let variant_results: Vec<VariantResult> = run_with_job_limit(jobs, || {
+        variant_paths
+            .par_iter()
+            .map(|variant_path| {
+                let variant_name = variant_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| variant_path.to_string_lossy().to_string());
+
+                let so_files = match find_shared_objects(variant_path) {
+                    Ok(so_files) => so_files,
+                    Err(e) => {
+                        return VariantResult {
+                            name: variant_name.clone(),
+                            is_compatible: false,
+                            violations: vec![SharedObjectViolation {
+                                file: variant_name,
+                                category: ViolationCategory::CheckError,
+                                subject: "check_error".to_string(),
+                                required: None,
+                                allowed: None,
+                                message: format!("Failed to find shared objects: {}", e),
+                            }],
+                            waived: Vec::new(),
+                            has_shared_objects: false,
+                            platform: "unknown".to_string(),
+                            max_glibc: None,
+                            max_glibcxx: None,
+                            max_cxxabi: None,
+                        };
+                    }
+                };
 
-            if !passed && show_violations {
-                variant_violations.push(SharedObjectViolation {
-                    message: violations_text,
-                });
-            }
-        }
+                let has_shared_objects = !so_files.is_empty();
+
+                // If no shared objects, mark as compatible and continue
+                if !has_shared_objects {
+                    return VariantResult {
+                        name: variant_name,
+                        is_compatible: true,
+                        violations: Vec::new(),
+                        waived: Vec::new(),
+                        has_shared_objects: false,
+                        platform: "none".to_string(),
+                        max_glibc: None,
+                        max_glibcxx: None,
+                        max_cxxabi: None,
+                    };
+                }
 
-        let is_compatible = variant_violations.is_empty();
-        variant_results.push(VariantResult {
-            name: variant_name,
-            is_compatible,
-            violations: variant_violations,
-            has_shared_objects: true,
-        });
-    }
+                // A variant's shared objects all target the same platform, so the first
+                // one found is representative for reporting purposes.
+                let variant_platform = target_platform_label(&so_files[0]).to_string();
+
+                // Check each shared object in the variant, in parallel. A per-file error
+                // (unreadable/unparsable .so) is recorded as a violation for that file
+                // rather than failing the whole variant. Compatibility is derived from
+                // `passed`, not from the (possibly empty, when `!show_violations`)
+                // detail lists, so a waived violation is the only thing that can turn a
+                // real failure into a pass.
+                let file_results: Vec<(bool, Vec<SharedObjectViolation>, Vec<SharedObjectViolation>)> =
+                    so_files
+                        .par_iter()
+                        .map(|so_path| match check_shared_object(
+                            so_path,
+                            platform,
+                            python_abi_version,
+                            macos_min,
+                            show_violations,
+                            cache_dir,
+                            no_cache,
+                            exceptions,
+                        ) {
+                            Ok(result) => result,
+                            Err(e) => (false, vec![SharedObjectViolation::check_error(so_path, &e)], Vec::new()),
+                        })
+                        .collect();
+
+                let is_compatible = file_results.iter().all(|(passed, _, _)| *passed);
+                let variant_violations: Vec<SharedObjectViolation> = file_results
+                    .iter()
+                    .flat_map(|(_, violations, _)| violations.clone())
+                    .collect();
+                let variant_waived: Vec<SharedObjectViolation> = file_results
+                    .into_iter()
+                    .flat_map(|(_, _, waived)| waived)
+                    .collect();
+
+                // Best-effort: a variant that fails this pass still reports its
+                // pass/fail verdict from `check_shared_object` above, it just loses
+                // the "how far over" detail.
+                let max_versions = variant_max_library_versions(&so_files).unwrap_or_default();
+
+                VariantResult {
+                    name: variant_name,
+                    is_compatible,
+                    violations: variant_violations,
+                    waived: variant_waived,
+                    has_shared_objects: true,
+                    platform: variant_platform,
+                    max_glibc: max_versions.get("GLIBC").map(|v| v.to_string()),
+                    max_glibcxx: max_versions.get("GLIBCXX").map(|v| v.to_string()),
+                    max_cxxabi: max_versions.get("CXXABI").map(|v| v.to_string()),
+                }
+            })
+            .collect()
+    })?;
 
     // Determine overall compatibility
     let overall_compatible = variant_results.iter().all(|result| result.is_compatible);
@@ -879,8 +2519,9 @@ pub fn check_abi_for_repository(
     Ok(AbiCheckResult {
         overall_compatible,
         variants: variant_results,
-        manylinux_version: manylinux_version.to_string(),
+        manylinux_version: platform.tag(),
         python_abi_version: python_abi_version.clone(),
+        macos_min_version: macos_min.clone(),
     })
 }
 
@@ -890,25 +2531,31 @@ pub fn process_repository(
     cache_dir: &Path,
     revision: &str,
     auto_fetch: bool,
-    manylinux: &str,
+    platform: &Platform,
     python_version: &Version,
+    macos_min: &Version,
     compact_output: bool,
     show_violations: bool,
+    no_cache: bool,
+    baseline: Option<&Path>,
+    bless: bool,
+    policy: Option<&Path>,
     format: Format,
+    jobs: Option<usize>,
 ) -> Result<()> {
     let repo_path = get_repo_path(repo_id, cache_dir);
 
     // Check if repository exists locally
     if !repo_path.exists() || !repo_path.join("refs/main").exists() {
         if auto_fetch {
-            if !format.is_json() {
+            if !format.is_json() && !format.is_junit() && !format.is_sarif() {
                 ConsoleFormatter::format_fetch_status(repo_id, true, None);
             }
 
             // Fetch the repository
             match fetch_repository(repo_id, cache_dir, revision) {
                 Ok(_) => {
-                    if !format.is_json() {
+                    if !format.is_json() && !format.is_junit() && !format.is_sarif() {
                         ConsoleFormatter::format_fetch_status(
                             repo_id,
                             false,
@@ -917,71 +2564,138 @@ pub fn process_repository(
                     }
                 }
                 Err(e) => {
-                    if !format.is_json() {
+                    if !format.is_json() && !format.is_junit() && !format.is_sarif() {
                         ConsoleFormatter::format_fetch_status(
                             repo_id,
                             false,
                             Some(&format!("fetch failed - {}", e)),
                         );
                         println!("---");
-                    } else {
+                    } else if format.is_json() {
+                        let hint = e.downcast_ref::<CompliantError>().and_then(|ce| ce.hint());
                         let error = RepoErrorResponse {
                             repository: repo_id.to_string(),
                             status: "fetch_failed".to_string(),
                             error: e.to_string(),
+                            hint,
                         };
                         println!(
                             "{}",
                             serde_json::to_string_pretty(&error)
                                 .context("Failed to serialize error response")?
                         );
+                    } else if format.is_sarif() {
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&render_sarif_error_report(
+                                repo_id,
+                                &format!("fetch failed: {}", e)
+                            ))
+                            .context("Failed to serialize SARIF report")?
+                        );
+                    } else {
+                        println!(
+                            "{}",
+                            render_junit_testsuite(&render_junit_error_testsuite(
+                                repo_id,
+                                &format!("fetch failed: {}", e)
+                            ))
+                        );
                     }
                     return Ok(());
                 }
             }
         } else {
+            let not_found = CompliantError::RepositoryNotFound(repo_id.to_string());
             // Print a message indicating the repository is missing
-            if !format.is_json() {
+            if !format.is_json() && !format.is_junit() && !format.is_sarif() {
                 ConsoleFormatter::format_missing_repo(repo_id);
-            } else {
+                if let Some(hint) = not_found.hint() {
+                    ConsoleFormatter::format_hint(hint);
+                }
+            } else if format.is_json() {
                 let error = RepoErrorResponse {
                     repository: repo_id.to_string(),
                     status: "not_found".to_string(),
                     error: "repository not found locally".to_string(),
+                    hint: not_found.hint(),
                 };
                 println!(
                     "{}",
                     serde_json::to_string_pretty(&error)
                         .context("Failed to serialize error response")?
                 );
+            } else if format.is_sarif() {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&render_sarif_error_report(
+                        repo_id,
+                        "repository not found locally"
+                    ))
+                    .context("Failed to serialize SARIF report")?
+                );
+            } else {
+                println!(
+                    "{}",
+                    render_junit_testsuite(&render_junit_error_testsuite(
+                        repo_id,
+                        "repository not found locally"
+                    ))
+                );
             }
 
-            return Err(CompliantError::RepositoryNotFound(repo_id.to_string()).into());
+            return Err(not_found.into());
         }
     }
 
     // Re-check after potential fetch
     let ref_file = repo_path.join("refs/main");
     if !ref_file.exists() {
+        let not_found = CompliantError::RepositoryNotFound(repo_id.to_string());
         // Print a message indicating the repository is missing
-        if !format.is_json() {
+        if !format.is_json() && !format.is_junit() && !format.is_sarif() {
             ConsoleFormatter::format_missing_repo(repo_id);
-        } else {
+            if let Some(hint) = not_found.hint() {
+                ConsoleFormatter::format_hint(hint);
+            }
+        } else if format.is_json() {
             let error = RepoErrorResponse {
                 repository: repo_id.to_string(),
                 status: "not_found".to_string(),
                 error: "repository not found locally".to_string(),
+                hint: not_found.hint(),
             };
             println!(
                 "{}",
                 serde_json::to_string_pretty(&error)
                     .context("Failed to serialize error response")?
             );
+        } else if format.is_sarif() {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&render_sarif_error_report(
+                    repo_id,
+                    "repository not found locally"
+                ))
+                .context("Failed to serialize SARIF report")?
+            );
+        } else {
+            println!(
+                "{}",
+                render_junit_testsuite(&render_junit_error_testsuite(
+                    repo_id,
+                    "repository not found locally"
+                ))
+            );
         }
 
-        return Err(CompliantError::RepositoryNotFound(repo_id.to_string()).into());
+        return Err(not_found.into());
     }
 
+    // Held for the rest of this function so a concurrent fetch can't swap the
+    // `main` ref or prune a snapshot out from under the checks below.
+    let _snapshot_lock = lockfile::lock_shared(&repo_path)?;
+
     let content = fs::read_to_string(&ref_file)
         .with_context(|| format!("Failed to read ref file: {:?}", ref_file))?;
 
@@ -989,50 +2703,101 @@ pub fn process_repository(
     let snapshot_dir = repo_path.join(format!("snapshots/{}", hash));
 
     if !snapshot_dir.exists() {
+        let not_found = CompliantError::RepositoryNotFound(format!(
+            "Snapshot not found for repository {}",
+            repo_id
+        ));
         // Print a message indicating the snapshot is missing
-        if !format.is_json() {
+        if !format.is_json() && !format.is_junit() && !format.is_sarif() {
             ConsoleFormatter::format_missing_repo(repo_id);
-        } else {
+            if let Some(hint) = not_found.hint() {
+                ConsoleFormatter::format_hint(hint);
+            }
+        } else if format.is_json() {
             let error = RepoErrorResponse {
                 repository: repo_id.to_string(),
                 status: "missing_snapshot".to_string(),
                 error: "snapshot not found".to_string(),
+                hint: not_found.hint(),
             };
             println!(
                 "{}",
                 serde_json::to_string_pretty(&error)
                     .context("Failed to serialize error response")?
             );
+        } else if format.is_sarif() {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&render_sarif_error_report(
+                    repo_id,
+                    "snapshot not found"
+                ))
+                .context("Failed to serialize SARIF report")?
+            );
+        } else {
+            println!(
+                "{}",
+                render_junit_testsuite(&render_junit_error_testsuite(repo_id, "snapshot not found"))
+            );
         }
 
-        return Err(CompliantError::RepositoryNotFound(format!(
-            "Snapshot not found for repository {}",
-            repo_id
-        ))
-        .into());
+        return Err(not_found.into());
     }
 
     let build_dir = snapshot_dir.join("build");
     if !build_dir.exists() {
+        let missing_build_dir = CompliantError::BuildDirNotFound(repo_id.to_string());
         // Print a message indicating the build directory is missing
-        if !format.is_json() {
+        if !format.is_json() && !format.is_junit() && !format.is_sarif() {
             ConsoleFormatter::format_missing_repo(repo_id);
-        } else {
+            if let Some(hint) = missing_build_dir.hint() {
+                ConsoleFormatter::format_hint(hint);
+            }
+        } else if format.is_json() {
             let error = RepoErrorResponse {
                 repository: repo_id.to_string(),
                 status: "missing_build_dir".to_string(),
                 error: "build directory not found".to_string(),
+                hint: missing_build_dir.hint(),
             };
             println!(
                 "{}",
                 serde_json::to_string_pretty(&error)
                     .context("Failed to serialize error response")?
             );
+        } else if format.is_sarif() {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&render_sarif_error_report(
+                    repo_id,
+                    "build directory not found"
+                ))
+                .context("Failed to serialize SARIF report")?
+            );
+        } else {
+            println!(
+                "{}",
+                render_junit_testsuite(&render_junit_error_testsuite(
+                    repo_id,
+                    "build directory not found"
+                ))
+            );
         }
 
-        return Err(CompliantError::BuildDirNotFound(repo_id.to_string()).into());
+        return Err(missing_build_dir.into());
     }
 
+    let policy = CompliancePolicy::resolve(policy, &snapshot_dir)
+        .context("Failed to resolve compliance policy")?;
+    let required_cuda_variants = policy
+        .as_ref()
+        .map(|p| p.cuda_variants(&COMPLIANT_VARIANTS.0))
+        .unwrap_or(&COMPLIANT_VARIANTS.0);
+    let required_rocm_variants = policy
+        .as_ref()
+        .map(|p| p.rocm_variants(&COMPLIANT_VARIANTS.1))
+        .unwrap_or(&COMPLIANT_VARIANTS.1);
+
     let variants = get_build_variants(&snapshot_dir).context("Failed to get build variants")?;
 
     let variant_strings: Vec<String> = variants.iter().map(|v| v.to_string()).collect();
@@ -1040,12 +2805,27 @@ pub fn process_repository(
     let build_status = get_build_status_summary(
         &build_dir,
         &variant_strings,
-        &COMPLIANT_VARIANTS.0,
-        &COMPLIANT_VARIANTS.1,
+        required_cuda_variants,
+        required_rocm_variants,
     );
 
+    let abi_exceptions = policy
+        .as_ref()
+        .map(|p| p.abi_exceptions())
+        .unwrap_or_default();
+
     let abi_output =
-        check_abi_for_repository(&snapshot_dir, manylinux, python_version, show_violations)
+        check_abi_for_repository(
+            &snapshot_dir,
+            platform,
+            python_version,
+            macos_min,
+            show_violations,
+            cache_dir,
+            no_cache,
+            &abi_exceptions,
+            jobs,
+        )
             .with_context(|| format!("Failed to check ABI compatibility for {}", repo_id))?;
 
     let abi_status = if abi_output.overall_compatible {
@@ -1055,15 +2835,13 @@ pub fn process_repository(
     };
 
     // Get present CUDA and ROCM variants
-    let cuda_variants_present_set: Vec<&String> = COMPLIANT_VARIANTS
-        .0
+    let cuda_variants_present_set: Vec<&String> = required_cuda_variants
         .iter()
         .filter(|v| variant_strings.contains(v))
         .collect();
 
     #[cfg(feature = "enable_rocm")]
-    let rocm_variants_present_set: Vec<&String> = COMPLIANT_VARIANTS
-        .1
+    let rocm_variants_present_set: Vec<&String> = required_rocm_variants
         .iter()
         .filter(|v| variant_strings.contains(v))
         .collect();
@@ -1072,100 +2850,909 @@ pub fn process_repository(
     let rocm_variants_present_set: Vec<&String> = Vec::new();
 
     // Check if all required variants are present
-    let cuda_compatible = cuda_variants_present_set.len() == COMPLIANT_VARIANTS.0.len();
+    let cuda_compatible = cuda_variants_present_set.len() == required_cuda_variants.len();
 
     #[cfg(feature = "enable_rocm")]
-    let rocm_compatible = rocm_variants_present_set.len() == COMPLIANT_VARIANTS.1.len();
+    let rocm_compatible = rocm_variants_present_set.len() == required_rocm_variants.len();
 
     #[cfg(not(feature = "enable_rocm"))]
     let rocm_compatible = true; // When ROCm is disabled, consider it compatible but unused
 
-    if format.is_json() {
-        // Create structured data for JSON output
-        let cuda_status = CudaStatus {
-            compatible: cuda_compatible,
-            present: cuda_variants_present_set
-                .iter()
-                .map(|&v| v.clone())
-                .collect(),
-            missing: COMPLIANT_VARIANTS
-                .0
-                .iter()
-                .filter(|v| !cuda_variants_present_set.contains(v))
-                .cloned()
-                .collect(),
-        };
+    // Built unconditionally (not just for `--format json`) since `--baseline` compares
+    // against it regardless of which format the caller asked to see on screen.
+    let cuda_status = CudaStatus {
+        compatible: cuda_compatible,
+        present: cuda_variants_present_set
+            .iter()
+            .map(|&v| v.clone())
+            .collect(),
+        missing: required_cuda_variants
+            .iter()
+            .filter(|v| !cuda_variants_present_set.contains(v))
+            .cloned()
+            .collect(),
+    };
 
-        #[cfg(feature = "enable_rocm")]
-        let rocm_status = Some(RocmStatus {
-            compatible: rocm_compatible,
-            present: rocm_variants_present_set
-                .iter()
-                .map(|&v| v.clone())
-                .collect(),
-            missing: COMPLIANT_VARIANTS
-                .1
-                .iter()
-                .filter(|v| !rocm_variants_present_set.contains(v))
-                .cloned()
-                .collect(),
-        });
+    #[cfg(feature = "enable_rocm")]
+    let rocm_status = Some(RocmStatus {
+        compatible: rocm_compatible,
+        present: rocm_variants_present_set
+            .iter()
+            .map(|&v| v.clone())
+            .collect(),
+        missing: required_rocm_variants
+            .iter()
+            .filter(|v| !rocm_variants_present_set.contains(v))
+            .cloned()
+            .collect(),
+    });
 
-        #[cfg(not(feature = "enable_rocm"))]
-        let rocm_status: Option<RocmStatus> = None;
+    #[cfg(not(feature = "enable_rocm"))]
+    let rocm_status: Option<RocmStatus> = None;
 
-        let variant_outputs: Vec<VariantCheckOutput> = abi_output
-            .variants
-            .iter()
-            .map(|v| VariantCheckOutput {
-                name: v.name.clone(),
-                compatible: v.is_compatible,
-                has_shared_objects: v.has_shared_objects,
-                violations: v
-                    .violations
-                    .iter()
-                    .map(|viol| viol.message.clone())
-                    .collect(),
-            })
-            .collect();
+    let variant_outputs: Vec<VariantCheckOutput> = abi_output
+        .variants
+        .iter()
+        .map(|v| VariantCheckOutput {
+            name: v.name.clone(),
+            compatible: v.is_compatible,
+            has_shared_objects: v.has_shared_objects,
+            platform: v.platform.clone(),
+            violations: v.violations.clone(),
+            waived: v.waived.clone(),
+            max_glibc: v.max_glibc.clone(),
+            max_glibcxx: v.max_glibcxx.clone(),
+            max_cxxabi: v.max_cxxabi.clone(),
+        })
+        .collect();
 
-        let result = RepositoryCheckResult {
-            repository: repo_id.to_string(),
-            status: "success".to_string(),
-            build_status: BuildStatus {
-                summary: build_status,
-                cuda: cuda_status,
-                rocm: rocm_status,
-            },
-            abi_status: AbiStatus {
-                compatible: abi_output.overall_compatible,
-                manylinux_version: abi_output.manylinux_version.clone(),
-                python_abi_version: abi_output.python_abi_version.to_string(),
-                variants: variant_outputs,
-            },
-        };
+    let check_result = RepositoryCheckResult {
+        repository: repo_id.to_string(),
+        status: "success".to_string(),
+        build_status: BuildStatus {
+            summary: build_status.clone(),
+            cuda: cuda_status,
+            rocm: rocm_status,
+        },
+        abi_status: AbiStatus {
+            compatible: abi_output.overall_compatible,
+            manylinux_version: abi_output.manylinux_version.clone(),
+            python_abi_version: abi_output.python_abi_version.to_string(),
+            macos_min_version: abi_output.macos_min_version.to_string(),
+            variants: variant_outputs,
+        },
+    };
 
-        // Output pretty-printed JSON
+    if format.is_sbom() {
+        let report = build_compliance_report(repo_id, &snapshot_dir, platform, python_version)?;
         println!(
             "{}",
-            serde_json::to_string_pretty(&result).context("Failed to serialize result")?
+            serde_json::to_string_pretty(&report).context("Failed to serialize compliance report")?
         );
-    } else {
+    } else if format.is_json() {
+        // Output pretty-printed JSON
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&check_result).context("Failed to serialize result")?
+        );
+    } else if format.is_junit() {
+        println!(
+            "{}",
+            render_junit_testsuite(&build_junit_testsuite(repo_id, &check_result))
+        );
+    } else if format.is_sarif() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&render_sarif_report(repo_id, &check_result))
+                .context("Failed to serialize SARIF report")?
+        );
+    } else {
         // Display console-formatted output via ConsoleFormatter
         ConsoleFormatter::format_repository_check_result(
             repo_id,
             &build_status,
             cuda_compatible,
             rocm_compatible,
-            &COMPLIANT_VARIANTS.0,
-            &COMPLIANT_VARIANTS.1,
+            required_cuda_variants,
+            required_rocm_variants,
             cuda_variants_present_set,
             rocm_variants_present_set,
             compact_output,
             &abi_output,
             abi_status,
+            show_violations,
+        );
+    }
+
+    if let Some(baseline_path) = baseline {
+        compare_against_baseline(baseline_path, &check_result, bless, format)?;
+    }
+
+    // With no policy, a check that finds missing variants or ABI violations
+    // still reports them in `check_result`/on screen but doesn't fail the
+    // process, matching this command's behavior before policies existed. A
+    // policy opts a repository into treating either outcome as a hard failure
+    // (the default once a policy is present) or explicitly downgrades it to a
+    // warning.
+    if let Some(policy) = &policy {
+        if !cuda_compatible || !rocm_compatible {
+            match policy.on_missing_variant {
+                Severity::Error => {
+                    return Err(CompliantError::Other(format!(
+                        "{} is missing required build variants (cuda compatible: {}, rocm compatible: {})",
+                        repo_id, cuda_compatible, rocm_compatible
+                    ))
+                    .into());
+                }
+                Severity::Warning => {
+                    ConsoleFormatter::format_hint(&format!(
+                        "{} is missing required build variants (policy severity: warning)",
+                        repo_id
+                    ));
+                }
+            }
+        }
+
+        if !abi_output.overall_compatible {
+            match policy.on_abi_violation {
+                Severity::Error => {
+                    return Err(CompliantError::AbiCheckError(format!(
+                        "{} is not ABI-compatible with the requested platform/Python ABI",
+                        repo_id
+                    ))
+                    .into());
+                }
+                Severity::Warning => {
+                    ConsoleFormatter::format_hint(&format!(
+                        "{} has ABI violations (policy severity: warning)",
+                        repo_id
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a repository's locally-cached snapshot directory, fetching it first if
+/// `auto_fetch` is set and it isn't cached yet. A smaller-scoped sibling of the
+/// fetch/not-found resolution inlined in `process_repository`, used by
+/// `process_repository_matrix`, which doesn't need `process_repository`'s
+/// per-format diagnostic output along the way.
+fn resolve_local_snapshot(
+    repo_id: &str,
+    cache_dir: &Path,
+    revision: &str,
+    auto_fetch: bool,
+) -> Result<PathBuf> {
+    let repo_path = get_repo_path(repo_id, cache_dir);
+
+    if (!repo_path.exists() || !repo_path.join("refs/main").exists()) && auto_fetch {
+        fetch_repository(repo_id, cache_dir, revision)?;
+    }
+
+    let ref_file = repo_path.join("refs/main");
+    if !ref_file.exists() {
+        return Err(CompliantError::RepositoryNotFound(repo_id.to_string()).into());
+    }
+
+    let _snapshot_lock = lockfile::lock_shared(&repo_path)?;
+
+    let content = fs::read_to_string(&ref_file)
+        .with_context(|| format!("Failed to read ref file: {:?}", ref_file))?;
+    let hash = content.trim();
+    let snapshot_dir = repo_path.join(format!("snapshots/{}", hash));
+
+    if !snapshot_dir.exists() {
+        return Err(CompliantError::RepositoryNotFound(format!(
+            "Snapshot not found for repository {}",
+            repo_id
+        ))
+        .into());
+    }
+
+    if !snapshot_dir.join("build").exists() {
+        return Err(CompliantError::BuildDirNotFound(repo_id.to_string()).into());
+    }
+
+    Ok(snapshot_dir)
+}
+
+/// Which expected CUDA/ROCm build variants a repository is missing, grouped by
+/// backend, computed purely from what's already present in its build directory.
+#[derive(Serialize)]
+pub struct MissingVariantsReport {
+    pub repository: String,
+    pub cuda: CudaStatus,
+    pub rocm: Option<RocmStatus>,
+}
+
+/// Diff a repository's on-disk build variants against the expected
+/// `COMPLIANT_VARIANTS` lists and report what's missing, grouped by backend.
+/// Unlike `process_repository`, this never runs the ABI checker and never
+/// re-fetches an already-present snapshot. It still uses the network when
+/// `COMPLIANT_VARIANTS` first downloads `build-variants.json`, and when
+/// `auto_fetch` fetches a snapshot that isn't cached.
+pub fn process_repository_list_missing(
+    repo_id: &str,
+    cache_dir: &Path,
+    revision: &str,
+    auto_fetch: bool,
+    format: Format,
+) -> Result<MissingVariantsReport> {
+    let snapshot_dir = resolve_local_snapshot(repo_id, cache_dir, revision, auto_fetch)?;
+
+    let variants = get_build_variants(&snapshot_dir).context("Failed to get build variants")?;
+    let variant_strings: Vec<String> = variants.iter().map(|v| v.to_string()).collect();
+
+    let cuda_present: Vec<String> = COMPLIANT_VARIANTS
+        .0
+        .iter()
+        .filter(|v| variant_strings.contains(v))
+        .cloned()
+        .collect();
+    let cuda = CudaStatus {
+        compatible: cuda_present.len() == COMPLIANT_VARIANTS.0.len(),
+        missing: COMPLIANT_VARIANTS
+            .0
+            .iter()
+            .filter(|v| !cuda_present.contains(v))
+            .cloned()
+            .collect(),
+        present: cuda_present,
+    };
+
+    #[cfg(feature = "enable_rocm")]
+    let rocm = {
+        let rocm_present: Vec<String> = COMPLIANT_VARIANTS
+            .1
+            .iter()
+            .filter(|v| variant_strings.contains(v))
+            .cloned()
+            .collect();
+        Some(RocmStatus {
+            compatible: rocm_present.len() == COMPLIANT_VARIANTS.1.len(),
+            missing: COMPLIANT_VARIANTS
+                .1
+                .iter()
+                .filter(|v| !rocm_present.contains(v))
+                .cloned()
+                .collect(),
+            present: rocm_present,
+        })
+    };
+
+    #[cfg(not(feature = "enable_rocm"))]
+    let rocm: Option<RocmStatus> = None;
+
+    let report = MissingVariantsReport {
+        repository: repo_id.to_string(),
+        cuda,
+        rocm,
+    };
+
+    if format.is_json() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("Failed to serialize result")?
+        );
+    } else {
+        ConsoleFormatter::format_missing_variants(&report);
+    }
+
+    Ok(report)
+}
+
+/// Verify a repository's cached files against the Hub's reported content
+/// hashes, optionally repairing mismatches by re-downloading just those files.
+/// Returns `Ok(())` once every file matches (after at most one repair attempt);
+/// otherwise propagates a `CompliantError::IntegrityError` for the first file
+/// still mismatched, after printing the full list.
+pub fn process_repository_verify(
+    repo_id: &str,
+    cache_dir: &Path,
+    revision: &str,
+    auto_fetch: bool,
+    redownload: bool,
+    format: Format,
+) -> Result<()> {
+    let snapshot_dir = resolve_local_snapshot(repo_id, cache_dir, revision, auto_fetch)?;
+
+    let rt = tokio::runtime::Runtime::new().context("Failed to create Tokio runtime")?;
+    let mut mismatches = rt.block_on(integrity::verify_repository(repo_id, &snapshot_dir, revision))?;
+
+    if !mismatches.is_empty() && redownload {
+        let files: Vec<String> = mismatches.iter().map(|m| m.file.clone()).collect();
+        rt.block_on(integrity::redownload_files(repo_id, revision, &files))
+            .context("Failed to re-download mismatched files")?;
+        mismatches = rt.block_on(integrity::verify_repository(repo_id, &snapshot_dir, revision))?;
+    }
+
+    if format.is_json() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&mismatches).context("Failed to serialize result")?
+        );
+    } else {
+        ConsoleFormatter::format_integrity_report(repo_id, &mismatches);
+    }
+
+    if let Some(first) = mismatches.into_iter().next() {
+        return Err(CompliantError::IntegrityError {
+            file: first.file,
+            expected: first.expected,
+            actual: first.actual,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// One cell of a compliance matrix: whether a build variant passed a specific
+/// (manylinux/musllinux policy, Python ABI) combination.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixCell {
+    pub policy: String,
+    pub python_abi: String,
+    pub compatible: bool,
+    pub violation_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixVariantRow {
+    pub variant: String,
+    pub cells: Vec<MatrixCell>,
+}
+
+#[derive(Serialize)]
+pub struct ComplianceMatrix {
+    pub repository: String,
+    pub rows: Vec<MatrixVariantRow>,
+    /// Whether the caller asked to see violation detail (`--show-violations`).
+    /// `MatrixCell::violation_count` is always the real count regardless of this
+    /// flag; this only controls whether the console tree prints it.
+    pub show_violations: bool,
+}
+
+/// Check a repository against the cross product of every `platform` and every
+/// `python_version`, and print the result as a matrix of build variant x policy x
+/// Python ABI, mirroring how a cross-compile test matrix runs one suite per target
+/// triple. Unlike `process_repository`, this always re-walks the repository's shared
+/// objects once per matrix cell, so it intentionally skips the build/CUDA/ROCm
+/// status and baseline machinery that only make sense for a single-cell check.
+#[allow(clippy::too_many_arguments)]
+pub fn process_repository_matrix(
+    repo_id: &str,
+    cache_dir: &Path,
+    revision: &str,
+    auto_fetch: bool,
+    platforms: &[Platform],
+    python_versions: &[Version],
+    macos_min: &Version,
+    show_violations: bool,
+    no_cache: bool,
+    format: Format,
+) -> Result<()> {
+    let snapshot_dir = resolve_local_snapshot(repo_id, cache_dir, revision, auto_fetch)?;
+    let abi_exceptions = CompliancePolicy::resolve(None, &snapshot_dir)
+        .context("Failed to resolve compliance policy")?
+        .map(|p| p.abi_exceptions())
+        .unwrap_or_default();
+
+    let mut by_variant: std::collections::BTreeMap<String, Vec<MatrixCell>> =
+        std::collections::BTreeMap::new();
+
+    for platform in platforms {
+        for python_version in python_versions {
+            // Always request violations here regardless of `show_violations`: the
+            // matrix needs the real `violations.len()` to report an accurate
+            // `violation_count` per cell. `show_violations` only controls whether
+            // the console output prints that count.
+            let abi_output = check_abi_for_repository(
+                &snapshot_dir,
+                platform,
+                python_version,
+                macos_min,
+                true,
+                cache_dir,
+                no_cache,
+                &abi_exceptions,
+                None,
+            )
+            .with_context(|| format!("Failed to check ABI compatibility for {}", repo_id))?;
+
+            for variant in &abi_output.variants {
+                by_variant
+                    .entry(variant.name.clone())
+                    .or_default()
+                    .push(MatrixCell {
+                        policy: platform.tag(),
+                        python_abi: python_version.to_string(),
+                        compatible: variant.is_compatible,
+                        violation_count: variant.violations.len(),
+                    });
+            }
+        }
+    }
+
+    let matrix = ComplianceMatrix {
+        repository: repo_id.to_string(),
+        rows: by_variant
+            .into_iter()
+            .map(|(variant, cells)| MatrixVariantRow { variant, cells })
+            .collect(),
+        show_violations,
+    };
+
+    if format.is_json() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&matrix).context("Failed to serialize compliance matrix")?
+        );
+    } else {
+        ConsoleFormatter::format_compliance_matrix(&matrix);
+    }
+
+    Ok(())
+}
+
+/// Compare `current` against a golden-file baseline, modeled on compiletest's
+/// "bless" mechanism: with no baseline on disk yet (or `--bless`), write `current`
+/// as the new baseline; otherwise compare and report any regression found, ignoring
+/// non-semantic differences like `present`/`missing`/violation ordering.
+fn compare_against_baseline(
+    baseline_path: &Path,
+    current: &RepositoryCheckResult,
+    bless: bool,
+    format: Format,
+) -> Result<()> {
+    if bless || !baseline_path.exists() {
+        let json = serde_json::to_string_pretty(current)
+            .context("Failed to serialize baseline result")?;
+        fs::write(baseline_path, json)
+            .with_context(|| format!("Failed to write baseline file: {:?}", baseline_path))?;
+        if !format.is_json() && !format.is_junit() && !format.is_sarif() {
+            println!("baseline written to {:?}", baseline_path);
+        }
+        return Ok(());
+    }
+
+    let baseline_json = fs::read_to_string(baseline_path)
+        .with_context(|| format!("Failed to read baseline file: {:?}", baseline_path))?;
+    let baseline: RepositoryCheckResult = serde_json::from_str(&baseline_json)
+        .with_context(|| format!("Failed to parse baseline file: {:?}", baseline_path))?;
+
+    let regressions = detect_baseline_regressions(&baseline, current);
+
+    if regressions.is_empty() {
+        if !format.is_json() && !format.is_junit() && !format.is_sarif() {
+            println!("no regressions relative to baseline {:?}", baseline_path);
+        }
+        return Ok(());
+    }
+
+    if !format.is_json() && !format.is_junit() && !format.is_sarif() {
+        println!("{}", "regressions relative to baseline:".red().bold());
+        for regression in &regressions {
+            println!("  - {}", regression);
+        }
+    } else {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&regressions)
+                .context("Failed to serialize baseline regressions")?
         );
     }
 
+    Err(anyhow::anyhow!(
+        "ABI compliance regressed relative to baseline {:?}",
+        baseline_path
+    ))
+}
+
+/// Compare two `RepositoryCheckResult`s and describe any regression in `current`
+/// relative to `baseline`: `overall_compatible` flipping to false, a required
+/// CUDA/ROCm variant that went missing, or a build variant that was compatible in
+/// the baseline and now has new violations. Ordering of `present`/`missing`/
+/// violation vectors is ignored.
+fn detect_baseline_regressions(
+    baseline: &RepositoryCheckResult,
+    current: &RepositoryCheckResult,
+) -> Vec<String> {
+    let mut regressions = Vec::new();
+
+    if baseline.abi_status.compatible && !current.abi_status.compatible {
+        regressions.push("overall ABI compatibility regressed from compatible to incompatible".to_string());
+    }
+
+    let missing_required = |before: &[String], after: &[String]| -> Vec<String> {
+        let after_set: std::collections::BTreeSet<&str> = after.iter().map(|s| s.as_str()).collect();
+        before
+            .iter()
+            .filter(|v| !after_set.contains(v.as_str()))
+            .cloned()
+            .collect()
+    };
+
+    for newly_missing in missing_required(
+        &baseline.build_status.cuda.present,
+        &current.build_status.cuda.present,
+    ) {
+        regressions.push(format!("CUDA variant {} is no longer present", newly_missing));
+    }
+
+    if let (Some(baseline_rocm), Some(current_rocm)) =
+        (&baseline.build_status.rocm, &current.build_status.rocm)
+    {
+        for newly_missing in missing_required(&baseline_rocm.present, &current_rocm.present) {
+            regressions.push(format!("ROCm variant {} is no longer present", newly_missing));
+        }
+    }
+
+    let current_variants: std::collections::HashMap<&str, &VariantCheckOutput> = current
+        .abi_status
+        .variants
+        .iter()
+        .map(|v| (v.name.as_str(), v))
+        .collect();
+
+    for baseline_variant in &baseline.abi_status.variants {
+        let Some(current_variant) = current_variants.get(baseline_variant.name.as_str()) else {
+            continue;
+        };
+
+        if baseline_variant.compatible && !current_variant.compatible {
+            let baseline_violations: std::collections::BTreeSet<&str> = baseline_variant
+                .violations
+                .iter()
+                .map(|v| v.message.as_str())
+                .collect();
+            let new_violations: Vec<&str> = current_variant
+                .violations
+                .iter()
+                .map(|v| v.message.as_str())
+                .filter(|v| !baseline_violations.contains(v))
+                .collect();
+
+            if new_violations.is_empty() {
+                regressions.push(format!(
+                    "variant {} regressed from compatible to incompatible",
+                    baseline_variant.name
+                ));
+            } else {
+                for violation in new_violations {
+                    regressions.push(format!(
+                        "variant {} gained new violation: {}",
+                        baseline_variant.name, violation
+                    ));
+                }
+            }
+        }
+    }
+
+    regressions
+}
+
+/// Resolve the on-disk snapshot directory for a repository at a specific revision,
+/// fetching it first if `auto_fetch` is set and it isn't cached locally yet. Unlike
+/// `process_repository`, which only ever resolves the `main` ref, this resolves
+/// `refs/<revision>` so that two different revisions of the same repository can be
+/// compared against each other. `revision` may also be a pinned 40-character commit
+/// hash, in which case it resolves straight to `snapshots/<revision>` without a ref
+/// lookup. An on-demand fetch only downloads the `build/` tree the ABI check
+/// actually reads, not the whole repository, and double-checks the ref it writes
+/// against the hash the Hub reports, so a fetch interrupted partway through can't
+/// leave behind a snapshot directory that looks complete but isn't.
+fn resolve_snapshot_dir(
+    repo_id: &str,
+    cache_dir: &Path,
+    revision: &str,
+    auto_fetch: bool,
+) -> Result<PathBuf> {
+    let repo_path = get_repo_path(repo_id, cache_dir);
+
+    let snapshot_dir = if is_commit_hash(revision) {
+        let snapshot_dir = repo_path.join("snapshots").join(revision);
+
+        if !snapshot_dir.exists() {
+            if auto_fetch {
+                fetch_build_tree(repo_id, cache_dir, revision).with_context(|| {
+                    format!("Failed to fetch repository {} at {}", repo_id, revision)
+                })?;
+            } else {
+                return Err(CompliantError::RepositoryNotFound(repo_id.to_string()).into());
+            }
+        }
+
+        snapshot_dir
+    } else {
+        let ref_file = repo_path.join("refs").join(revision);
+
+        if !ref_file.exists() {
+            if auto_fetch {
+                fetch_build_tree(repo_id, cache_dir, revision).with_context(|| {
+                    format!("Failed to fetch repository {} at {}", repo_id, revision)
+                })?;
+            } else {
+                return Err(CompliantError::RepositoryNotFound(repo_id.to_string()).into());
+            }
+        }
+
+        if !ref_file.exists() {
+            return Err(CompliantError::RepositoryNotFound(format!(
+                "Snapshot not found for repository {} at revision {}",
+                repo_id, revision
+            ))
+            .into());
+        }
+
+        let _snapshot_lock = lockfile::lock_shared(&repo_path)?;
+
+        let content = fs::read_to_string(&ref_file)
+            .with_context(|| format!("Failed to read ref file: {:?}", ref_file))?;
+        let hash = content.trim();
+        repo_path.join(format!("snapshots/{}", hash))
+    };
+
+    if !snapshot_dir.exists() {
+        return Err(CompliantError::RepositoryNotFound(format!(
+            "Snapshot not found for repository {} at revision {}",
+            repo_id, revision
+        ))
+        .into());
+    }
+
+    let build_dir = snapshot_dir.join("build");
+    if !build_dir.exists() {
+        return Err(CompliantError::BuildDirNotFound(repo_id.to_string()).into());
+    }
+
+    Ok(snapshot_dir)
+}
+
+/// How a build variant's compliance changed between two revisions of a repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantDiffStatus {
+    /// Present in the newer revision only.
+    Added,
+    /// Present in the older revision only.
+    Removed,
+    /// Present in both, compatibility unchanged.
+    Unchanged,
+    /// Was compatible in the older revision, is not in the newer one.
+    Regressed,
+    /// Was not compatible in the older revision, is in the newer one.
+    Fixed,
+}
+
+#[derive(Serialize)]
+pub struct VariantDiffEntry {
+    pub name: String,
+    pub status: VariantDiffStatus,
+    pub violations_added: Vec<String>,
+    pub violations_resolved: Vec<String>,
+}
+
+/// A structured comparison of ABI compliance between two revisions of a repository,
+/// for PR/release gating: a maintainer can see at a glance whether a rebuild broke
+/// manylinux or Python-ABI compliance relative to the previously published revision.
+#[derive(Serialize)]
+pub struct DiffResult {
+    pub repository: String,
+    pub revision_a: String,
+    pub revision_b: String,
+    pub variants: Vec<VariantDiffEntry>,
+    pub has_regressions: bool,
+}
+
+/// The full set of build-variant names (CUDA and ROCm) a policy requires,
+/// falling back to the built-in `COMPLIANT_VARIANTS` defaults when `policy` is
+/// `None` -- same fallback `process_repository` uses for a single-revision check.
+fn required_variant_names(policy: Option<&CompliancePolicy>) -> std::collections::BTreeSet<String> {
+    let cuda = policy
+        .map(|p| p.cuda_variants(&COMPLIANT_VARIANTS.0))
+        .unwrap_or(&COMPLIANT_VARIANTS.0);
+    let rocm = policy
+        .map(|p| p.rocm_variants(&COMPLIANT_VARIANTS.1))
+        .unwrap_or(&COMPLIANT_VARIANTS.1);
+    cuda.iter().chain(rocm.iter()).cloned().collect()
+}
+
+/// Classify every build variant present in either `before` or `after` as
+/// Added/Removed/Unchanged/Regressed/Fixed, and for variants present in both, list
+/// the violation messages newly introduced vs. newly resolved.
+fn diff_variants(before: &[VariantResult], after: &[VariantResult]) -> Vec<VariantDiffEntry> {
+    let before_by_name: std::collections::HashMap<&str, &VariantResult> =
+        before.iter().map(|v| (v.name.as_str(), v)).collect();
+    let after_by_name: std::collections::HashMap<&str, &VariantResult> =
+        after.iter().map(|v| (v.name.as_str(), v)).collect();
+
+    let mut names: Vec<&str> = before_by_name
+        .keys()
+        .chain(after_by_name.keys())
+        .copied()
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| match (before_by_name.get(name), after_by_name.get(name)) {
+            (Some(_), None) => VariantDiffEntry {
+                name: name.to_string(),
+                status: VariantDiffStatus::Removed,
+                violations_added: Vec::new(),
+                violations_resolved: Vec::new(),
+            },
+            (None, Some(_)) => VariantDiffEntry {
+                name: name.to_string(),
+                status: VariantDiffStatus::Added,
+                violations_added: Vec::new(),
+                violations_resolved: Vec::new(),
+            },
+            (Some(before_variant), Some(after_variant)) => {
+                let before_messages: std::collections::BTreeSet<&str> = before_variant
+                    .violations
+                    .iter()
+                    .map(|v| v.message.as_str())
+                    .collect();
+                let after_messages: std::collections::BTreeSet<&str> = after_variant
+                    .violations
+                    .iter()
+                    .map(|v| v.message.as_str())
+                    .collect();
+
+                let status = match (before_variant.is_compatible, after_variant.is_compatible) {
+                    (true, false) => VariantDiffStatus::Regressed,
+                    (false, true) => VariantDiffStatus::Fixed,
+                    _ => VariantDiffStatus::Unchanged,
+                };
+
+                VariantDiffEntry {
+                    name: name.to_string(),
+                    status,
+                    violations_added: after_messages
+                        .difference(&before_messages)
+                        .map(|s| s.to_string())
+                        .collect(),
+                    violations_resolved: before_messages
+                        .difference(&after_messages)
+                        .map(|s| s.to_string())
+                        .collect(),
+                }
+            }
+            (None, None) => unreachable!("name came from one of the two maps"),
+        })
+        .collect()
+}
+
+/// Compare ABI compliance for a repository between two revisions and emit a
+/// structured diff rather than two independent reports. Returns an error if any
+/// variant regressed, or a required CUDA/ROCm variant is newly missing, so CI can
+/// gate on the process exit status.
+#[allow(clippy::too_many_arguments)]
+pub fn process_repository_diff(
+    repo_id: &str,
+    cache_dir: &Path,
+    revision_a: &str,
+    revision_b: &str,
+    auto_fetch: bool,
+    platform: &Platform,
+    python_version: &Version,
+    macos_min: &Version,
+    no_cache: bool,
+    format: Format,
+) -> Result<()> {
+    let snapshot_a = resolve_snapshot_dir(repo_id, cache_dir, revision_a, auto_fetch)
+        .with_context(|| format!("Failed to resolve revision {} for {}", revision_a, repo_id))?;
+    let snapshot_b = resolve_snapshot_dir(repo_id, cache_dir, revision_b, auto_fetch)
+        .with_context(|| format!("Failed to resolve revision {} for {}", revision_b, repo_id))?;
+
+    // Each revision resolves its own `compliance.toml`, since the policy itself may
+    // have changed between the two revisions being compared.
+    let policy_a = CompliancePolicy::resolve(None, &snapshot_a)
+        .context("Failed to resolve compliance policy")?;
+    let policy_b = CompliancePolicy::resolve(None, &snapshot_b)
+        .context("Failed to resolve compliance policy")?;
+    let exceptions_a = policy_a.as_ref().map(|p| p.abi_exceptions()).unwrap_or_default();
+    let exceptions_b = policy_b.as_ref().map(|p| p.abi_exceptions()).unwrap_or_default();
+
+    // Union both revisions' required-variant lists: a variant that was required
+    // at A and is gone at B is a regression even if B's policy also happens to no
+    // longer require it.
+    let required_variants: std::collections::BTreeSet<String> =
+        required_variant_names(policy_a.as_ref())
+            .into_iter()
+            .chain(required_variant_names(policy_b.as_ref()))
+            .collect();
+
+    let abi_a = check_abi_for_repository(
+        &snapshot_a,
+        platform,
+        python_version,
+        macos_min,
+        true,
+        cache_dir,
+        no_cache,
+        &exceptions_a,
+        None,
+    )
+    .with_context(|| format!("Failed to check ABI compatibility for {} at {}", repo_id, revision_a))?;
+
+    let abi_b = check_abi_for_repository(
+        &snapshot_b,
+        platform,
+        python_version,
+        macos_min,
+        true,
+        cache_dir,
+        no_cache,
+        &exceptions_b,
+        None,
+    )
+    .with_context(|| format!("Failed to check ABI compatibility for {} at {}", repo_id, revision_b))?;
+
+    let variants = diff_variants(&abi_a.variants, &abi_b.variants);
+    let has_regressions = variants.iter().any(|v| {
+        v.status == VariantDiffStatus::Regressed
+            || (v.status == VariantDiffStatus::Removed && required_variants.contains(&v.name))
+    });
+
+    let diff_result = DiffResult {
+        repository: repo_id.to_string(),
+        revision_a: revision_a.to_string(),
+        revision_b: revision_b.to_string(),
+        variants,
+        has_regressions,
+    };
+
+    if format.is_json() {
+        #[derive(Serialize)]
+        struct FlatDiff {
+            added: Vec<String>,
+            removed: Vec<String>,
+        }
+
+        let flat = FlatDiff {
+            added: diff_result
+                .variants
+                .iter()
+                .flat_map(|v| {
+                    v.violations_added
+                        .iter()
+                        .map(move |message| format!("{}: {}", v.name, message))
+                })
+                .collect(),
+            removed: diff_result
+                .variants
+                .iter()
+                .flat_map(|v| {
+                    v.violations_resolved
+                        .iter()
+                        .map(move |message| format!("{}: {}", v.name, message))
+                })
+                .collect(),
+        };
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&flat).context("Failed to serialize diff result")?
+        );
+    } else {
+        ConsoleFormatter::format_diff_result(&diff_result);
+    }
+
+    if has_regressions {
+        return Err(anyhow::anyhow!(
+            "ABI compliance regressed for {} between {} and {}",
+            repo_id,
+            revision_a,
+            revision_b
+        ));
+    }
+
     Ok(())
 }
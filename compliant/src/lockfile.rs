@@ -0,0 +1,94 @@
+//! Advisory file locking around per-repository cache mutation.
+//!
+//! `fetch_repository_async` and the snapshot reads in `process_repository` both
+//! touch the same `<repo_path>/refs/<revision>` and `<repo_path>/snapshots/<hash>`
+//! files. Two concurrent invocations of this tool (a CI matrix checking several
+//! repos in parallel, or this tool running alongside `huggingface-cli`) can race
+//! on those files and leave a reader looking at a half-written snapshot. Guard
+//! against that with a `<repo_path>/.lock` file: an exclusive lock while
+//! downloading and writing refs, a shared lock while reading a snapshot.
+
+use std::fs::{self, File};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use fs4::FileExt;
+
+/// How long to wait for a lock before giving up and proceeding unlocked.
+/// Overridable so CI with many parallel jobs against one cache dir can wait
+/// longer than the default.
+pub fn lock_timeout() -> Duration {
+    std::env::var("COMPLIANT_LOCK_TIMEOUT_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(30))
+}
+
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Holds `<repo_path>/.lock` open for as long as the guard is alive; the lock is
+/// released when it's dropped.
+pub struct RepoLock(File);
+
+impl Drop for RepoLock {
+    fn drop(&mut self) {
+        // Best-effort: the OS also releases the lock when the fd closes, so a
+        // failure here just means we did that release a moment early.
+        let _ = FileExt::unlock(&self.0);
+    }
+}
+
+/// Acquire an exclusive lock on `repo_path`'s lock file before mutating its cache
+/// contents (downloading files, writing refs).
+pub fn lock_exclusive(repo_path: &Path) -> Result<Option<RepoLock>> {
+    acquire(repo_path, true)
+}
+
+/// Acquire a shared lock on `repo_path`'s lock file before reading a snapshot, so
+/// a concurrent fetch can't leave us looking at a half-written file.
+pub fn lock_shared(repo_path: &Path) -> Result<Option<RepoLock>> {
+    acquire(repo_path, false)
+}
+
+/// Returns `Ok(None)` instead of an error when the lock could not be taken,
+/// whether because the filesystem doesn't support advisory locking (some network
+/// mounts) or because the timeout elapsed — callers should warn and proceed
+/// unlocked rather than abort a compliance check over a lock.
+fn acquire(repo_path: &Path, exclusive: bool) -> Result<Option<RepoLock>> {
+    fs::create_dir_all(repo_path)
+        .with_context(|| format!("Failed to create repo directory: {:?}", repo_path))?;
+    let lock_path = repo_path.join(".lock");
+    let file = File::create(&lock_path)
+        .with_context(|| format!("Failed to open lock file: {:?}", lock_path))?;
+
+    let timeout = lock_timeout();
+    let deadline = Instant::now() + timeout;
+    loop {
+        let result = if exclusive {
+            file.try_lock_exclusive()
+        } else {
+            file.try_lock_shared()
+        };
+
+        match result {
+            Ok(()) => return Ok(Some(RepoLock(file))),
+            Err(e) if e.kind() == std::io::ErrorKind::Unsupported => {
+                eprintln!(
+                    "warning: filesystem does not support locking {:?}; proceeding without a lock",
+                    lock_path
+                );
+                return Ok(None);
+            }
+            Err(_) if Instant::now() >= deadline => {
+                eprintln!(
+                    "warning: timed out after {:?} waiting for a lock on {:?}; proceeding without a lock",
+                    timeout, lock_path
+                );
+                return Ok(None);
+            }
+            Err(_) => std::thread::sleep(LOCK_POLL_INTERVAL),
+        }
+    }
+}
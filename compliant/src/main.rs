@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use clap::Parser;
+use colored::Colorize;
 use kernel_abi_check::Version;
 use std::str::FromStr;
 
@@ -19,26 +20,114 @@ fn main() -> Result<()> {
         compliant::Commands::Check {
             repos,
             manylinux,
+            musllinux,
             python_abi,
+            macos_min,
             auto_fetch,
             revision,
             long,
             show_violations,
+            no_cache,
+            verify,
+            baseline,
+            bless,
+            policy,
             format,
+            jobs,
         } => {
             // Check repositories for compliance
             check_repositories(
                 &repos,
                 &cache_dir,
                 &manylinux,
+                musllinux.as_deref(),
                 &python_abi,
+                &macos_min,
                 auto_fetch,
                 &revision,
                 long,
                 show_violations,
+                no_cache,
+                verify,
+                baseline.as_deref(),
+                bless,
+                policy.as_deref(),
                 format,
+                jobs,
             )?;
         }
+
+        compliant::Commands::Serve {
+            bind,
+            port,
+            concurrency,
+            auth_token,
+        } => {
+            let addr: std::net::SocketAddr = format!("{}:{}", bind, port)
+                .parse()
+                .with_context(|| format!("Invalid bind address {}:{}", bind, port))?;
+
+            let rt = tokio::runtime::Runtime::new().context("Failed to create Tokio runtime")?;
+            rt.block_on(compliant::run_server(
+                addr,
+                cache_dir.clone(),
+                concurrency,
+                auth_token,
+            ))?;
+        }
+
+        compliant::Commands::Diff {
+            repo,
+            revision_a,
+            revision_b,
+            manylinux,
+            musllinux,
+            python_abi,
+            macos_min,
+            auto_fetch,
+            no_cache,
+            format,
+        } => {
+            diff_repository(
+                &repo,
+                &cache_dir,
+                &revision_a,
+                &revision_b,
+                &manylinux,
+                musllinux.as_deref(),
+                &python_abi,
+                &macos_min,
+                auto_fetch,
+                no_cache,
+                format,
+            )?;
+        }
+
+        compliant::Commands::Verify {
+            repo,
+            revision,
+            auto_fetch,
+            redownload,
+            format,
+        } => {
+            compliant::process_repository_verify(
+                &repo,
+                &cache_dir,
+                &revision,
+                auto_fetch,
+                redownload,
+                format,
+            )?;
+        }
+
+        compliant::Commands::ListMissing {
+            repos,
+            revision,
+            auto_fetch,
+            format,
+        } => {
+            list_missing_variants(&repos, &cache_dir, &revision, auto_fetch, format)?;
+        }
     }
 
     Ok(())
@@ -91,16 +180,25 @@ fn list_repositories(cache_dir: &std::path::Path, format: compliant::Format) ->
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn check_repositories(
     repos: &str,
     cache_dir: &std::path::Path,
     manylinux: &str,
+    musllinux: Option<&str>,
     python_abi: &str,
+    macos_min: &str,
     auto_fetch: bool,
     revision: &str,
     long: bool,
     show_violations: bool,
+    no_cache: bool,
+    verify: bool,
+    baseline: Option<&str>,
+    bless: bool,
+    policy: Option<&str>,
     format: compliant::Format,
+    jobs: Option<usize>,
 ) -> Result<()> {
     let repositories: Vec<String> = repos
         .split(',')
@@ -129,22 +227,93 @@ fn check_repositories(
         return Ok(());
     }
 
-    let python_version = Version::from_str(python_abi)
-        .map_err(|e| anyhow::anyhow!("Invalid Python ABI version {}: {}", python_abi, e))?;
+    let python_versions: Vec<Version> = python_abi
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|v| {
+            Version::from_str(v).map_err(|e| anyhow::anyhow!("Invalid Python ABI version {}: {}", v, e))
+        })
+        .collect::<Result<_>>()?;
+
+    let macos_min_version = Version::from_str(macos_min)
+        .map_err(|e| anyhow::anyhow!("Invalid macOS version {}: {}", macos_min, e))?;
+
+    // A `--musllinux` threshold takes precedence over `--manylinux`: a variant is
+    // either glibc- or musl-based, never both.
+    let platforms: Vec<compliant::Platform> = match musllinux {
+        Some(musllinux) => {
+            let version = Version::from_str(musllinux).map_err(|e| {
+                anyhow::anyhow!("Invalid musllinux version {}: {}", musllinux, e)
+            })?;
+            vec![compliant::Platform::Musllinux(version)]
+        }
+        None => manylinux
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|policy| compliant::Platform::Manylinux(policy.to_string()))
+            .collect(),
+    };
 
     for repo_id in &repositories {
-        if let Err(e) = compliant::process_repository(
-            repo_id,
-            cache_dir,
-            revision,
-            auto_fetch,
-            manylinux,
-            &python_version,
-            !long,
-            show_violations,
-            format,
-        ) {
+        if verify {
+            // Catch a truncated or corrupted download here, before it surfaces
+            // as a confusing parse error deep inside the ABI check below.
+            if let Err(e) =
+                compliant::process_repository_verify(repo_id, cache_dir, revision, auto_fetch, true, format)
+            {
+                eprintln!("Error verifying repository {}: {}", repo_id, e);
+                if let Some(hint) = e.downcast_ref::<compliant::CompliantError>().and_then(|ce| ce.hint()) {
+                    eprintln!("{} {}", "help:".cyan().bold(), hint);
+                }
+                continue;
+            }
+        }
+
+        // A single policy and a single Python ABI is the common case: run the full
+        // single-cell check, with its console/JSON/JUnit/SBOM output and baseline
+        // support. Passing more than one of either switches to the compliance
+        // matrix, which checks every (policy, Python ABI) combination but doesn't
+        // carry build status, CUDA/ROCm, or baseline comparison along with it.
+        let result = if platforms.len() == 1 && python_versions.len() == 1 {
+            compliant::process_repository(
+                repo_id,
+                cache_dir,
+                revision,
+                auto_fetch,
+                &platforms[0],
+                &python_versions[0],
+                &macos_min_version,
+                !long,
+                show_violations,
+                no_cache,
+                baseline.map(std::path::Path::new),
+                bless,
+                policy.map(std::path::Path::new),
+                format,
+                jobs,
+            )
+        } else {
+            compliant::process_repository_matrix(
+                repo_id,
+                cache_dir,
+                revision,
+                auto_fetch,
+                &platforms,
+                &python_versions,
+                &macos_min_version,
+                show_violations,
+                no_cache,
+                format,
+            )
+        };
+
+        if let Err(e) = result {
             eprintln!("Error processing repository {}: {}", repo_id, e);
+            if let Some(hint) = e.downcast_ref::<compliant::CompliantError>().and_then(|ce| ce.hint()) {
+                eprintln!("{} {}", "help:".cyan().bold(), hint);
+            }
 
             // Continue processing other repositories rather than exiting early
             // This is more user-friendly for batch processing
@@ -153,3 +322,81 @@ fn check_repositories(
 
     Ok(())
 }
+
+fn list_missing_variants(
+    repos: &str,
+    cache_dir: &std::path::Path,
+    revision: &str,
+    auto_fetch: bool,
+    format: compliant::Format,
+) -> Result<()> {
+    let repositories: Vec<String> = repos
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if repositories.is_empty() {
+        eprintln!("no repository ids provided");
+        return Ok(());
+    }
+
+    for repo_id in &repositories {
+        if let Err(e) =
+            compliant::process_repository_list_missing(repo_id, cache_dir, revision, auto_fetch, format)
+        {
+            eprintln!("Error processing repository {}: {}", repo_id, e);
+            if let Some(hint) = e.downcast_ref::<compliant::CompliantError>().and_then(|ce| ce.hint()) {
+                eprintln!("{} {}", "help:".cyan().bold(), hint);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn diff_repository(
+    repo_id: &str,
+    cache_dir: &std::path::Path,
+    revision_a: &str,
+    revision_b: &str,
+    manylinux: &str,
+    musllinux: Option<&str>,
+    python_abi: &str,
+    macos_min: &str,
+    auto_fetch: bool,
+    no_cache: bool,
+    format: compliant::Format,
+) -> Result<()> {
+    let python_version = Version::from_str(python_abi)
+        .map_err(|e| anyhow::anyhow!("Invalid Python ABI version {}: {}", python_abi, e))?;
+
+    let macos_min_version = Version::from_str(macos_min)
+        .map_err(|e| anyhow::anyhow!("Invalid macOS version {}: {}", macos_min, e))?;
+
+    // A `--musllinux` threshold takes precedence over `--manylinux`: a variant is
+    // either glibc- or musl-based, never both.
+    let platform = match musllinux {
+        Some(musllinux) => {
+            let version = Version::from_str(musllinux).map_err(|e| {
+                anyhow::anyhow!("Invalid musllinux version {}: {}", musllinux, e)
+            })?;
+            compliant::Platform::Musllinux(version)
+        }
+        None => compliant::Platform::Manylinux(manylinux.to_string()),
+    };
+
+    compliant::process_repository_diff(
+        repo_id,
+        cache_dir,
+        revision_a,
+        revision_b,
+        auto_fetch,
+        &platform,
+        &python_version,
+        &macos_min_version,
+        no_cache,
+        format,
+    )
+}
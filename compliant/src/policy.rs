@@ -0,0 +1,104 @@
+//! Declarative per-repository compliance policy.
+//!
+//! `COMPLIANT_VARIANTS` hardcodes a single "required variants" list for every
+//! repository this tool checks, which doesn't work once different repos need
+//! to be held to different standards (a repo still shipping only a subset of
+//! CUDA variants during a migration, say). A `compliance.toml` manifest lets a
+//! repository declare its own required variant lists and decide whether a
+//! missing variant or an ABI violation is a hard failure or just a warning.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Whether a failed requirement should fail the check or just be reported.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Default for Severity {
+    fn default() -> Self {
+        Severity::Error
+    }
+}
+
+/// Parsed `compliance.toml`. Every field is optional: an absent
+/// `required_*_variants` list falls back to the built-in `COMPLIANT_VARIANTS`
+/// defaults, and an absent severity defaults to `Error`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CompliancePolicy {
+    /// Required CUDA build variants. Falls back to `COMPLIANT_VARIANTS.0` when absent.
+    #[serde(default)]
+    pub required_cuda_variants: Option<Vec<String>>,
+    /// Required ROCm build variants. Falls back to `COMPLIANT_VARIANTS.1` when absent.
+    #[serde(default)]
+    pub required_rocm_variants: Option<Vec<String>>,
+    /// Declared target manylinux version. Parsed but not read by any check:
+    /// `--manylinux` alone sets the platform a check runs against.
+    #[serde(default)]
+    pub manylinux_version: Option<String>,
+    /// Declared target Python ABI version. Parsed but not read by any check,
+    /// same as `manylinux_version` above.
+    #[serde(default)]
+    pub python_abi_version: Option<String>,
+    /// Severity of a missing required CUDA/ROCm variant.
+    #[serde(default)]
+    pub on_missing_variant: Severity,
+    /// Severity of an ABI violation (manylinux/python-ABI/macOS-min).
+    #[serde(default)]
+    pub on_abi_violation: Severity,
+    /// Symbol names pre-approved despite exceeding the platform's allowed
+    /// version, e.g. `["CXXABI_1.3.15"]`. Supports `*` wildcards.
+    #[serde(default)]
+    pub allowed_symbols: Vec<String>,
+    /// Linked library names pre-approved despite falling outside the target
+    /// platform's baseline, e.g. `["libfoo.so.*"]`. Supports `*` wildcards.
+    #[serde(default)]
+    pub allowed_libraries: Vec<String>,
+}
+
+impl CompliancePolicy {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read compliance policy: {:?}", path))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse compliance policy: {:?}", path))
+    }
+
+    /// Resolve the policy to use for a check: an explicit `--policy` path
+    /// always wins; otherwise fall back to a `compliance.toml` at the
+    /// snapshot root if one exists; otherwise there is no policy and callers
+    /// should fall back to the built-in defaults.
+    pub fn resolve(explicit_path: Option<&Path>, snapshot_dir: &Path) -> Result<Option<Self>> {
+        if let Some(path) = explicit_path {
+            return Ok(Some(Self::load(path)?));
+        }
+
+        let default_path = snapshot_dir.join("compliance.toml");
+        if default_path.exists() {
+            return Ok(Some(Self::load(&default_path)?));
+        }
+
+        Ok(None)
+    }
+
+    pub fn cuda_variants<'a>(&'a self, defaults: &'a [String]) -> &'a [String] {
+        self.required_cuda_variants.as_deref().unwrap_or(defaults)
+    }
+
+    pub fn rocm_variants<'a>(&'a self, defaults: &'a [String]) -> &'a [String] {
+        self.required_rocm_variants.as_deref().unwrap_or(defaults)
+    }
+
+    pub fn abi_exceptions(&self) -> crate::AbiExceptions {
+        crate::AbiExceptions {
+            allowed_symbols: self.allowed_symbols.clone(),
+            allowed_libraries: self.allowed_libraries.clone(),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! HTTP server exposing compliance checks over `GET /check`, so CI systems and
+//! dashboards can query kernel ABI compatibility without shelling out to the CLI.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Json};
+use axum::routing::get;
+use axum::Router;
+use kernel_abi_check::Version;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
+
+use crate::{
+    check_abi_for_repository, fetch_repository_async, get_repo_path, AbiCheckResult,
+    AbiExceptions, Platform, DEFAULT_MACOS_MIN,
+};
+
+struct ServerState {
+    cache_dir: PathBuf,
+    concurrency: Semaphore,
+    auth_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CheckQuery {
+    repos: String,
+    #[serde(default = "default_manylinux")]
+    manylinux: String,
+    musllinux: Option<String>,
+    #[serde(default = "default_python_abi")]
+    python_abi: String,
+    #[serde(default = "default_revision")]
+    revision: String,
+}
+
+fn default_manylinux() -> String {
+    "manylinux_2_28".to_string()
+}
+
+fn default_python_abi() -> String {
+    "3.9".to_string()
+}
+
+fn default_revision() -> String {
+    "main".to_string()
+}
+
+#[derive(Serialize)]
+struct RepoAbiCheckResponse {
+    repository: String,
+    abi: AbiCheckResult,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    repository: String,
+    error: String,
+}
+
+fn bad_request(repository: &str, error: String) -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorBody {
+            repository: repository.to_string(),
+            error,
+        }),
+    )
+        .into_response()
+}
+
+/// Check the caller's `Authorization: Bearer <token>` header against the server's
+/// configured token, if one was configured. `/check` drives ABI audits against
+/// attacker-named repository content, so it must not be reachable anonymously
+/// once the server is bound to anything but loopback.
+fn is_authorized(state: &ServerState, headers: &HeaderMap) -> bool {
+    let Some(expected) = &state.auth_token else {
+        return true;
+    };
+    let Some(header) = headers.get(axum::http::header::AUTHORIZATION) else {
+        return false;
+    };
+    let Ok(header) = header.to_str() else {
+        return false;
+    };
+    header
+        .strip_prefix("Bearer ")
+        .is_some_and(|token| token == expected)
+}
+
+async fn check_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Query(query): Query<CheckQuery>,
+) -> axum::response::Response {
+    if !is_authorized(&state, &headers) {
+        return (StatusCode::UNAUTHORIZED, "missing or invalid bearer token").into_response();
+    }
+
+    let repos: Vec<String> = query
+        .repos
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if repos.is_empty() {
+        return bad_request("", "no repository ids provided".to_string());
+    }
+
+    let python_version = match Version::from_str(&query.python_abi) {
+        Ok(version) => version,
+        Err(e) => {
+            return bad_request(
+                &query.repos,
+                format!("invalid python_abi {}: {}", query.python_abi, e),
+            );
+        }
+    };
+
+    let platform = match &query.musllinux {
+        Some(musllinux) => match Version::from_str(musllinux) {
+            Ok(version) => Platform::Musllinux(version),
+            Err(e) => {
+                return bad_request(
+                    &query.repos,
+                    format!("invalid musllinux version {}: {}", musllinux, e),
+                );
+            }
+        },
+        None => Platform::Manylinux(query.manylinux.clone()),
+    };
+
+    // Bound the number of repository checks running at once; each one parses and
+    // audits every shared object in a repo's build directory.
+    let _permit = match state.concurrency.acquire().await {
+        Ok(permit) => permit,
+        Err(_) => {
+            return (StatusCode::SERVICE_UNAVAILABLE, "server is shutting down").into_response();
+        }
+    };
+
+    let mut results = Vec::with_capacity(repos.len());
+    for repo_id in &repos {
+        match check_repo(&state.cache_dir, repo_id, &query.revision, &platform, &python_version).await {
+            Ok(abi) => results.push(RepoAbiCheckResponse {
+                repository: repo_id.clone(),
+                abi,
+            }),
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorBody {
+                        repository: repo_id.clone(),
+                        error: e.to_string(),
+                    }),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    Json(results).into_response()
+}
+
+/// Fetch the repository if it isn't cached locally, then run the same ABI audit
+/// `check` runs from the CLI and hand back the `Serialize`-able result.
+async fn check_repo(
+    cache_dir: &std::path::Path,
+    repo_id: &str,
+    revision: &str,
+    platform: &Platform,
+    python_version: &Version,
+) -> anyhow::Result<AbiCheckResult> {
+    let repo_path = get_repo_path(repo_id, cache_dir);
+
+    if !repo_path.exists() || !repo_path.join(format!("refs/{revision}")).exists() {
+        fetch_repository_async(repo_id, cache_dir, revision).await?;
+    }
+
+    let ref_file = repo_path.join(format!("refs/{revision}"));
+    let content = tokio::fs::read_to_string(&ref_file)
+        .await
+        .with_context(|| format!("Failed to read ref file: {:?}", ref_file))?;
+
+    let hash = content.trim();
+    let snapshot_dir = repo_path.join(format!("snapshots/{}", hash));
+
+    // Parsing and auditing the shared objects is blocking, CPU-bound work (it
+    // hands off to a rayon pool internally); run it off the async executor.
+    let cache_dir = cache_dir.to_path_buf();
+    let snapshot_dir = snapshot_dir.clone();
+    let platform = platform.clone();
+    let python_version = python_version.clone();
+    tokio::task::spawn_blocking(move || {
+        check_abi_for_repository(
+            &snapshot_dir,
+            &platform,
+            &python_version,
+            &DEFAULT_MACOS_MIN,
+            true,
+            &cache_dir,
+            false,
+            &AbiExceptions::default(),
+            None,
+        )
+    })
+    .await
+    .context("ABI check task panicked")?
+}
+
+/// Start the HTTP server and serve `GET /check` until the process is terminated.
+///
+/// Refuses to bind to a non-loopback address without an `auth_token`: `/check` runs
+/// ABI audits (including musllinux `PT_INTERP` loader detection) against whatever
+/// repository content the caller names, so leaving it open to the network with no
+/// authentication turns it into a remotely triggerable code-execution surface.
+pub async fn run_server(
+    bind_addr: SocketAddr,
+    cache_dir: PathBuf,
+    concurrency: usize,
+    auth_token: Option<String>,
+) -> anyhow::Result<()> {
+    if auth_token.is_none() && !bind_addr.ip().is_loopback() {
+        anyhow::bail!(
+            "refusing to bind {} without --auth-token/KERNEL_BUILDER_SERVE_TOKEN: \
+             /check must not be reachable anonymously from the network",
+            bind_addr
+        );
+    }
+
+    let state = Arc::new(ServerState {
+        cache_dir,
+        concurrency: Semaphore::new(concurrency.max(1)),
+        auth_token,
+    });
+
+    let app = Router::new()
+        .route("/check", get(check_handler))
+        .with_state(state);
+
+    let listener = TcpListener::bind(bind_addr)
+        .await
+        .with_context(|| format!("Failed to bind server address {}", bind_addr))?;
+
+    println!("listening on {}", bind_addr);
+    axum::serve(listener, app).await.context("Server error")?;
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+//! Real ELF symbol-version auditing.
+//!
+//! Walks the `.gnu.version_r` (`VERNEED`) section of an ELF shared object, resolving
+//! names through `.dynstr`, to recover every versioned dependency it actually imports,
+//! e.g. `GLIBC_2.28`, `GLIBCXX_3.4.25`, or `CXXABI_1.3.11`. The dynamic symbol table
+//! itself is not read, so which symbols need each version is not recorded. This mirrors what `auditwheel`
+//! does when it inspects a wheel's compiled extensions, rather than trusting a
+//! caller-supplied version string.
+
+use std::collections::HashMap;
+
+use eyre::{eyre, Result};
+use object::{Endianness, Object, ObjectSection};
+
+use crate::Version;
+
+/// The maximum version required of a single versioned library (e.g. `GLIBC`),
+/// along with the full version name it was read from (e.g. `GLIBC_2.28`). The
+/// individual symbols that need that version are not recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryRequirement {
+    pub library: String,
+    pub version: Version,
+    pub symbol: String,
+}
+
+/// Parse the trailing `x.y[.z]` component of a versioned symbol name like
+/// `GLIBC_2.28` or `GLIBCXX_3.4.25` into a `Version`.
+fn parse_version_suffix(name: &str) -> Option<Version> {
+    let version_str = name.split('_').next_back()?;
+    version_str.parse().ok()
+}
+
+/// Walk the ELF `.gnu.version_r` (VERNEED) section of `file` and return every
+/// versioned symbol dependency it records, with the library name stripped of its
+/// version suffix (e.g. `GLIBC_2.28` -> library `GLIBC`).
+pub fn collect_version_requirements(
+    data: &[u8],
+    endianness: Endianness,
+) -> Result<Vec<LibraryRequirement>> {
+    let file = object::File::parse(data).map_err(|e| eyre!("Cannot parse object file: {e}"))?;
+
+    let version_r = match file.section_by_name(".gnu.version_r") {
+        Some(section) => section,
+        None => return Ok(Vec::new()),
+    };
+    let dynstr = match file.section_by_name(".dynstr") {
+        Some(section) => section,
+        None => return Ok(Vec::new()),
+    };
+
+    let verneed_data = version_r
+        .data()
+        .map_err(|e| eyre!("Cannot read .gnu.version_r section: {e}"))?;
+    let dynstr_data = dynstr
+        .data()
+        .map_err(|e| eyre!("Cannot read .dynstr section: {e}"))?;
+
+    Ok(parse_verneed_requirements(
+        verneed_data,
+        dynstr_data,
+        endianness.is_little_endian(),
+    ))
+}
+
+/// Walk a raw `.gnu.version_r` (VERNEED) buffer, resolving each `Vernaux` entry's
+/// name against a raw `.dynstr` buffer. Split out from [`collect_version_requirements`]
+/// so the byte-offset parsing can be unit tested against synthetic buffers without
+/// needing a full ELF object to parse.
+fn parse_verneed_requirements(
+    verneed_data: &[u8],
+    dynstr_data: &[u8],
+    is_le: bool,
+) -> Vec<LibraryRequirement> {
+    let read_u16 = |buf: &[u8]| -> u16 {
+        let bytes: [u8; 2] = buf[..2].try_into().unwrap();
+        if is_le {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        }
+    };
+    let read_u32 = |buf: &[u8]| -> u32 {
+        let bytes: [u8; 4] = buf[..4].try_into().unwrap();
+        if is_le {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        }
+    };
+    let read_cstr = |offset: usize| -> String {
+        dynstr_data[offset..]
+            .split(|&b| b == 0)
+            .next()
+            .map(|bytes| String::from_utf8_lossy(bytes).to_string())
+            .unwrap_or_default()
+    };
+
+    // Elf{32,64}_Verneed and Elf{32,64}_Vernaux have the same layout on both word
+    // sizes: 16 bytes, four 32-bit words each (vn_version/vn_cnt are u16 packed into
+    // the first word of Verneed). Per the ELF spec, `vna_name` is the third word of
+    // Vernaux (offset 8..12) -- offset 4..8 is `vna_flags` packed with `vna_other`,
+    // not the string table offset.
+    let mut requirements = Vec::new();
+    let mut entry_offset = 0usize;
+    loop {
+        if entry_offset + 16 > verneed_data.len() {
+            break;
+        }
+        let verneed = &verneed_data[entry_offset..];
+        let vn_cnt = read_u16(&verneed[2..4]);
+        let vn_aux = read_u32(&verneed[8..12]);
+        let vn_next = read_u32(&verneed[12..16]);
+
+        let mut aux_offset = entry_offset + vn_aux as usize;
+        for _ in 0..vn_cnt {
+            if aux_offset + 16 > verneed_data.len() {
+                break;
+            }
+            let vernaux = &verneed_data[aux_offset..];
+            let vna_name = read_u32(&vernaux[8..12]);
+            let vna_next = read_u32(&vernaux[12..16]);
+
+            let symbol_version = read_cstr(vna_name as usize);
+            if let Some(version) = parse_version_suffix(&symbol_version) {
+                let library = symbol_version
+                    .rsplit_once('_')
+                    .map(|(lib, _)| lib.to_string())
+                    .unwrap_or(symbol_version.clone());
+                requirements.push(LibraryRequirement {
+                    library,
+                    version,
+                    symbol: symbol_version,
+                });
+            }
+
+            if vna_next == 0 {
+                break;
+            }
+            aux_offset += vna_next as usize;
+        }
+
+        if vn_next == 0 {
+            break;
+        }
+        entry_offset += vn_next as usize;
+    }
+
+    requirements
+}
+
+/// Group a flat list of requirements by library name, keeping only the maximum
+/// version required of each.
+pub fn max_requirement_per_library(
+    requirements: &[LibraryRequirement],
+) -> HashMap<String, LibraryRequirement> {
+    let mut maxima: HashMap<String, LibraryRequirement> = HashMap::new();
+
+    for requirement in requirements {
+        maxima
+            .entry(requirement.library.clone())
+            .and_modify(|existing| {
+                if requirement.version > existing.version {
+                    *existing = requirement.clone();
+                }
+            })
+            .or_insert_with(|| requirement.clone());
+    }
+
+    maxima
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a synthetic `.gnu.version_r` buffer with a single `Elf64_Verneed`
+    /// entry pointing at the given `Elf64_Vernaux` entries (each `(vna_name, vna_next)`
+    /// pair: an offset into `.dynstr`, and the byte distance from this entry to the next).
+    fn build_verneed(auxs: &[(u32, u32)]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        // Elf64_Verneed: vn_version(u16), vn_cnt(u16), vn_file(u32), vn_aux(u32), vn_next(u32)
+        buf.extend_from_slice(&1u16.to_le_bytes()); // vn_version
+        buf.extend_from_slice(&(auxs.len() as u16).to_le_bytes()); // vn_cnt
+        buf.extend_from_slice(&0u32.to_le_bytes()); // vn_file (unused by the parser)
+        buf.extend_from_slice(&16u32.to_le_bytes()); // vn_aux: right after this 16-byte entry
+        buf.extend_from_slice(&0u32.to_le_bytes()); // vn_next: no further Verneed entries
+
+        for &(vna_name, vna_next) in auxs {
+            // Elf64_Vernaux: vna_hash(u32), vna_flags(u16), vna_other(u16), vna_name(u32), vna_next(u32)
+            buf.extend_from_slice(&0u32.to_le_bytes()); // vna_hash (unused by the parser)
+            buf.extend_from_slice(&0u16.to_le_bytes()); // vna_flags
+            buf.extend_from_slice(&0u16.to_le_bytes()); // vna_other
+            buf.extend_from_slice(&vna_name.to_le_bytes());
+            buf.extend_from_slice(&vna_next.to_le_bytes());
+        }
+
+        buf
+    }
+
+    /// Build a `.dynstr`-style buffer: a leading NUL (index 0 is always empty),
+    /// followed by each string NUL-terminated. Returns the buffer and each
+    /// string's offset into it.
+    fn build_dynstr(strings: &[&str]) -> (Vec<u8>, Vec<u32>) {
+        let mut buf = vec![0u8];
+        let mut offsets = Vec::new();
+        for s in strings {
+            offsets.push(buf.len() as u32);
+            buf.extend_from_slice(s.as_bytes());
+            buf.push(0);
+        }
+        (buf, offsets)
+    }
+
+    #[test]
+    fn parses_vna_name_at_the_correct_offset() {
+        let (dynstr, offsets) = build_dynstr(&["GLIBC_2.17", "GLIBC_2.28"]);
+        let verneed = build_verneed(&[(offsets[0], 16), (offsets[1], 0)]);
+
+        let requirements = parse_verneed_requirements(&verneed, &dynstr, true);
+
+        assert_eq!(requirements.len(), 2);
+        assert_eq!(requirements[0].symbol, "GLIBC_2.17");
+        assert_eq!(requirements[0].library, "GLIBC");
+        assert_eq!(
+            requirements[0].version,
+            Version {
+                major: 2,
+                minor: 17,
+                patch: 0
+            }
+        );
+        assert_eq!(requirements[1].symbol, "GLIBC_2.28");
+
+        let maxima = max_requirement_per_library(&requirements);
+        let glibc = maxima.get("GLIBC").expect("GLIBC requirement");
+        assert_eq!(
+            glibc.version,
+            Version {
+                major: 2,
+                minor: 28,
+                patch: 0
+            }
+        );
+    }
+
+    #[test]
+    fn skips_entries_with_unparseable_symbol_versions() {
+        let (dynstr, offsets) = build_dynstr(&["not_a_version_string"]);
+        let verneed = build_verneed(&[(offsets[0], 0)]);
+
+        let requirements = parse_verneed_requirements(&verneed, &dynstr, true);
+
+        assert!(requirements.is_empty());
+    }
+}
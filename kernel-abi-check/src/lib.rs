@@ -0,0 +1,33 @@
+pub mod elf_audit;
+mod macho_audit;
+mod pe_audit;
+mod policy;
+pub mod version;
+
+use std::collections::HashMap;
+
+use eyre::Result;
+use object::Endianness;
+
+pub use elf_audit::LibraryRequirement;
+pub use macho_audit::check_macos_min;
+pub use pe_audit::check_pe_baseline;
+pub use policy::{check_manylinux, check_python_abi, Violation};
+pub use version::Version;
+
+/// The maximum required version of each versioned library (`GLIBC`, `GLIBCXX`,
+/// `CXXABI`, ...) a shared object actually imports, independent of any policy.
+/// Useful for reporting "how far over the line" a binary is, or for detecting the
+/// platform tag a binary demands.
+///
+/// A thin wrapper over [`elf_audit::collect_version_requirements`] and
+/// [`elf_audit::max_requirement_per_library`] with no extra logic of its own, so the
+/// offset-parsing and max-aggregation regression tests live with those functions in
+/// `elf_audit`.
+pub fn max_library_versions(data: &[u8], endianness: Endianness) -> Result<HashMap<String, Version>> {
+    let requirements = elf_audit::collect_version_requirements(data, endianness)?;
+    Ok(elf_audit::max_requirement_per_library(&requirements)
+        .into_iter()
+        .map(|(library, requirement)| (library, requirement.version))
+        .collect())
+}
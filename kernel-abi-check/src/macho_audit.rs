@@ -0,0 +1,117 @@
+//! Mach-O minimum-OS-version and framework-import auditing, mirroring
+//! `elf_audit`'s approach of walking a format's own metadata rather than trusting a
+//! caller-supplied target.
+
+use eyre::{eyre, Result};
+use object::macho::LoadCommandVariant;
+use object::read::macho::MachHeader;
+use object::File;
+
+use crate::Version;
+
+/// Dylib/framework paths a macOS binary is always allowed to link against without
+/// tying the wheel to a specific SDK or vendored library.
+const ALLOWED_SYSTEM_PREFIXES: &[&str] = &[
+    "/usr/lib/libSystem",
+    "/usr/lib/libc++",
+    "/usr/lib/libobjc",
+    "/System/Library/Frameworks/",
+];
+
+/// Mach-O packs an `X.Y.Z` version as `(X << 16) | (Y << 8) | Z`.
+fn decode_packed_version(raw: u32) -> Version {
+    Version {
+        major: (raw >> 16) as usize,
+        minor: ((raw >> 8) & 0xff) as usize,
+        patch: (raw & 0xff) as usize,
+    }
+}
+
+/// The minimum OS version a Mach-O binary declares via the legacy
+/// `LC_VERSION_MIN_MACOSX` command or the newer `LC_BUILD_VERSION`. Returns `None`
+/// for non-Mach-O files or Mach-O files that declare neither.
+pub fn min_os_version(file: &File) -> Result<Option<Version>> {
+    match file {
+        File::MachO32(inner) => min_os_version_from_header(inner.macho_header(), inner.endian(), inner.data()),
+        File::MachO64(inner) => min_os_version_from_header(inner.macho_header(), inner.endian(), inner.data()),
+        _ => Ok(None),
+    }
+}
+
+fn min_os_version_from_header<H: MachHeader>(
+    header: &H,
+    endian: H::Endian,
+    data: &[u8],
+) -> Result<Option<Version>> {
+    let mut commands = header
+        .load_commands(endian, data, 0)
+        .map_err(|e| eyre!("Cannot read Mach-O load commands: {e}"))?;
+
+    while let Some(command) = commands
+        .next()
+        .map_err(|e| eyre!("Cannot read Mach-O load command: {e}"))?
+    {
+        match command
+            .variant()
+            .map_err(|e| eyre!("Cannot decode Mach-O load command: {e}"))?
+        {
+            LoadCommandVariant::VersionMin(cmd) => {
+                return Ok(Some(decode_packed_version(cmd.version.get(endian))));
+            }
+            LoadCommandVariant::BuildVersion(cmd) => {
+                return Ok(Some(decode_packed_version(cmd.minos.get(endian))));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// Audit a Mach-O binary's declared minimum OS version and imported libraries
+/// against a requested `--macos-min` target, returning every violation found.
+/// Not a Mach-O file returns an empty list rather than an error, since the caller
+/// dispatches by `file.format()` before calling this.
+pub fn check_macos_min(macos_min: &Version, file: &File) -> Result<Vec<String>> {
+    let mut violations = Vec::new();
+
+    if let Some(declared_min) = min_os_version(file)? {
+        if declared_min > *macos_min {
+            violations.push(format!(
+                "declares minimum OS version {} which exceeds --macos-min {}",
+                declared_min, macos_min
+            ));
+        }
+    }
+
+    for library in undefined_libraries_outside_system_frameworks(file)? {
+        violations.push(format!(
+            "links against {} which is outside the allowed system frameworks",
+            library
+        ));
+    }
+
+    Ok(violations)
+}
+
+/// Linked libraries that fall outside Apple's own system frameworks, which would
+/// tie the binary to something that isn't guaranteed present on every target.
+fn undefined_libraries_outside_system_frameworks(file: &File) -> Result<Vec<String>> {
+    use object::Object;
+
+    let mut offenders: Vec<String> = file
+        .imports()
+        .map_err(|e| eyre!("Cannot read Mach-O imports: {e}"))?
+        .iter()
+        .map(|import| String::from_utf8_lossy(import.library()).to_string())
+        .filter(|library| {
+            !ALLOWED_SYSTEM_PREFIXES
+                .iter()
+                .any(|prefix| library.starts_with(prefix))
+        })
+        .collect();
+
+    offenders.sort();
+    offenders.dedup();
+    Ok(offenders)
+}
@@ -0,0 +1,86 @@
+//! PE (Windows) subsystem and import auditing.
+
+use eyre::{eyre, Result};
+use object::pe::{ImageNtHeaders32, ImageNtHeaders64};
+use object::read::pe::ImageNtHeaders;
+use object::{File, LittleEndian as LE, Object};
+
+/// DLL name prefixes (case-insensitive) a kernel's PE variant is always allowed to
+/// import without tying it to a specific toolchain or vendored library.
+const ALLOWED_DLL_PREFIXES: &[&str] = &[
+    "kernel32",
+    "user32",
+    "advapi32",
+    "msvcrt",
+    "ucrtbase",
+    "vcruntime",
+    "api-ms-win-",
+    "python3",
+];
+
+fn subsystem_name(subsystem: u16) -> &'static str {
+    match subsystem {
+        object::pe::IMAGE_SUBSYSTEM_WINDOWS_GUI => "windows",
+        object::pe::IMAGE_SUBSYSTEM_WINDOWS_CUI => "console",
+        _ => "unknown",
+    }
+}
+
+fn machine_name<Nt: ImageNtHeaders>(nt_headers: &Nt) -> String {
+    format!("{:#06x}", nt_headers.file_header().machine.get(LE))
+}
+
+fn imported_dlls(file: &File) -> Result<Vec<String>> {
+    let mut dlls: Vec<String> = file
+        .imports()
+        .map_err(|e| eyre!("Cannot read PE imports: {e}"))?
+        .iter()
+        .map(|import| String::from_utf8_lossy(import.library()).to_string())
+        .collect();
+    dlls.sort();
+    dlls.dedup();
+    Ok(dlls)
+}
+
+/// Audit a PE binary's subsystem and imported DLLs. The machine type is only
+/// named in the subsystem violation message; it is not checked. Not a PE file
+/// returns an empty list rather than an error, since the caller dispatches by
+/// `file.format()` before calling this.
+pub fn check_pe_baseline(file: &File) -> Result<Vec<String>> {
+    let (machine, subsystem) = match file {
+        File::Pe32(inner) => {
+            let nt = inner.nt_headers();
+            (
+                machine_name::<ImageNtHeaders32>(nt),
+                subsystem_name(nt.optional_header().subsystem.get(LE)),
+            )
+        }
+        File::Pe64(inner) => {
+            let nt = inner.nt_headers();
+            (
+                machine_name::<ImageNtHeaders64>(nt),
+                subsystem_name(nt.optional_header().subsystem.get(LE)),
+            )
+        }
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut violations = Vec::new();
+    if subsystem != "console" && subsystem != "windows" {
+        violations.push(format!(
+            "unexpected PE subsystem {} for machine {}",
+            subsystem, machine
+        ));
+    }
+
+    for dll in imported_dlls(file)? {
+        let allowed = ALLOWED_DLL_PREFIXES
+            .iter()
+            .any(|prefix| dll.to_lowercase().starts_with(prefix));
+        if !allowed {
+            violations.push(format!("imports {} which is outside the allowed DLL baseline", dll));
+        }
+    }
+
+    Ok(violations)
+}
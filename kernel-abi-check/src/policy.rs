@@ -0,0 +1,142 @@
+//! Manylinux policy table and the violation types produced by auditing a shared
+//! object against it.
+
+use std::collections::HashMap;
+
+use eyre::Result;
+use object::Endianness;
+
+use crate::elf_audit::{collect_version_requirements, max_requirement_per_library};
+use crate::Version;
+
+/// A single symbol-version requirement that exceeds the ceiling allowed by a
+/// manylinux policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub library: String,
+    pub symbol: String,
+    pub required: Version,
+    pub allowed: Version,
+}
+
+/// The maximum `GLIBC`/`GLIBCXX`/`CXXABI` versions permitted by a manylinux tag.
+struct ManylinuxPolicy {
+    glibc: Version,
+    glibcxx: Version,
+    cxxabi: Version,
+}
+
+fn version(major: usize, minor: usize, patch: usize) -> Version {
+    Version {
+        major,
+        minor,
+        patch,
+    }
+}
+
+/// Built-in policy table mapping each supported `manylinux_*` tag to the ceiling
+/// versions it permits. Mirrors the table `auditwheel` ships for glibc-based
+/// platform tags.
+fn manylinux_policy(manylinux_version: &str) -> Result<ManylinuxPolicy> {
+    let policy = match manylinux_version {
+        "manylinux1" | "manylinux_2_5" => ManylinuxPolicy {
+            glibc: version(2, 5, 0),
+            glibcxx: version(3, 4, 8),
+            cxxabi: version(1, 3, 0),
+        },
+        "manylinux2010" | "manylinux_2_12" => ManylinuxPolicy {
+            glibc: version(2, 12, 0),
+            glibcxx: version(3, 4, 13),
+            cxxabi: version(1, 3, 3),
+        },
+        "manylinux2014" | "manylinux_2_17" => ManylinuxPolicy {
+            glibc: version(2, 17, 0),
+            glibcxx: version(3, 4, 19),
+            cxxabi: version(1, 3, 7),
+        },
+        "manylinux_2_24" => ManylinuxPolicy {
+            glibc: version(2, 24, 0),
+            glibcxx: version(3, 4, 25),
+            cxxabi: version(1, 3, 11),
+        },
+        "manylinux_2_28" => ManylinuxPolicy {
+            glibc: version(2, 28, 0),
+            glibcxx: version(3, 4, 26),
+            cxxabi: version(1, 3, 11),
+        },
+        "manylinux_2_31" => ManylinuxPolicy {
+            glibc: version(2, 31, 0),
+            glibcxx: version(3, 4, 28),
+            cxxabi: version(1, 3, 12),
+        },
+        "manylinux_2_34" => ManylinuxPolicy {
+            glibc: version(2, 34, 0),
+            glibcxx: version(3, 4, 30),
+            cxxabi: version(1, 3, 13),
+        },
+        other => eyre::bail!("Unknown manylinux policy: {other}"),
+    };
+
+    Ok(policy)
+}
+
+fn ceiling_for_library(policy: &ManylinuxPolicy, library: &str) -> Option<Version> {
+    match library {
+        "GLIBC" => Some(policy.glibc),
+        "GLIBCXX" => Some(policy.glibcxx),
+        "CXXABI" => Some(policy.cxxabi),
+        _ => None,
+    }
+}
+
+/// Audit a shared object's versioned dynamic-symbol requirements against a
+/// manylinux policy, returning every library whose maximum required version
+/// exceeds what the policy permits.
+pub fn check_manylinux(manylinux_version: &str, data: &[u8], endianness: Endianness) -> Result<Vec<Violation>> {
+    let policy = manylinux_policy(manylinux_version)?;
+    let requirements = collect_version_requirements(data, endianness)?;
+    let maxima = max_requirement_per_library(&requirements);
+
+    Ok(violations_against_policy(&maxima, |library| {
+        ceiling_for_library(&policy, library)
+    }))
+}
+
+fn violations_against_policy(
+    maxima: &HashMap<String, crate::elf_audit::LibraryRequirement>,
+    ceiling_for: impl Fn(&str) -> Option<Version>,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for requirement in maxima.values() {
+        if let Some(allowed) = ceiling_for(&requirement.library) {
+            if requirement.version > allowed {
+                violations.push(Violation {
+                    library: requirement.library.clone(),
+                    symbol: requirement.symbol.clone(),
+                    required: requirement.version,
+                    allowed,
+                });
+            }
+        }
+    }
+
+    violations.sort_by(|a, b| a.library.cmp(&b.library));
+    violations
+}
+
+/// Audit a shared object's Python-ABI versioned symbol requirements (`Py_...`
+/// tagged `PYTHON_<major>.<minor>` in CPython's stable-ABI symbol versioning)
+/// against the requested interpreter ABI version.
+pub fn check_python_abi(python_abi_version: &Version, data: &[u8], endianness: Endianness) -> Result<Vec<Violation>> {
+    let requirements = collect_version_requirements(data, endianness)?;
+    let maxima = max_requirement_per_library(&requirements);
+
+    Ok(violations_against_policy(&maxima, |library| {
+        if library == "PYTHON" {
+            Some(*python_abi_version)
+        } else {
+            None
+        }
+    }))
+}
@@ -6,6 +6,8 @@ use std::path::Path;
 fn main() {
     // Print for debugging and to ensure the script reruns when changed
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build-variants.json");
+    println!("cargo:rerun-if-env-changed=KERNEL_BUILDER_REFRESH_VARIANTS");
 
     // Get the output directory from Cargo
     let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
@@ -14,33 +16,43 @@ fn main() {
     println!("cargo:warning=Build script is running!");
     println!("cargo:warning=Output directory: {out_dir}");
 
-    // Fetch the remote JSON file
-    println!("cargo:warning=Fetching remote variants JSON...");
-    let url = "https://raw.githubusercontent.com/huggingface/kernel-builder/refs/heads/main/build-variants.json";
+    // A committed, vendored copy of build-variants.json is the default, so builds
+    // are reproducible offline and never silently fall back to reporting zero CUDA/
+    // ROCm variants just because the build host can't reach GitHub. Only attempt
+    // the network fetch when a refresh was explicitly requested.
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let vendored_path = Path::new(&manifest_dir).join("build-variants.json");
+    let vendored_variants_json = fs::read_to_string(&vendored_path)
+        .unwrap_or_else(|e| panic!("Failed to read vendored variants file {vendored_path:?}: {e}"));
 
-    let mut remote_variants_json = String::new();
+    let remote_variants_json = if env::var("KERNEL_BUILDER_REFRESH_VARIANTS").as_deref() == Ok("1") {
+        println!("cargo:warning=Fetching remote variants JSON...");
+        let url = "https://raw.githubusercontent.com/huggingface/kernel-builder/refs/heads/main/build-variants.json";
 
-    match ureq::get(url).call() {
-        Ok(resp) => {
-            match resp.into_reader().read_to_string(&mut remote_variants_json) {
+        let mut fetched = String::new();
+
+        match ureq::get(url).call() {
+            Ok(resp) => match resp.into_reader().read_to_string(&mut fetched) {
                 Ok(_) => {
                     println!(
                         "cargo:warning=Successfully fetched remote variants ({} bytes)",
-                        remote_variants_json.len()
+                        fetched.len()
                     );
+                    fetched
                 }
                 Err(e) => {
-                    println!("cargo:warning=Error reading response body: {e}");
-                    // Instead of returning an empty JSON, provide fallback content
-                    remote_variants_json = String::from("{}");
+                    println!("cargo:warning=Error reading response body: {e}, using vendored copy");
+                    vendored_variants_json
                 }
+            },
+            Err(e) => {
+                println!("cargo:warning=Error fetching remote variants: {e}, using vendored copy");
+                vendored_variants_json
             }
         }
-        Err(e) => {
-            println!("cargo:warning=Error fetching remote variants: {e}");
-            // Provide fallback content
-            remote_variants_json = String::from("{}");
-        }
+    } else {
+        println!("cargo:warning=Using vendored variants JSON (set KERNEL_BUILDER_REFRESH_VARIANTS=1 to fetch the latest)");
+        vendored_variants_json
     };
 
     // Create output directory if it doesn't exist (though Cargo should have created it)
@@ -56,9 +68,23 @@ pub const VARIANTS_DATA: &str = r#"{}"#;
 // Use OnceLock to lazily initialize the parsed JSON data
 static VARIANTS_CACHE: OnceLock<Value> = OnceLock::new();
 
-// Function to get the parsed JSON data
+// Function to get the parsed JSON data. Honors KERNEL_BUILDER_VARIANTS_PATH, so a
+// local file can override the vendored/fetched-at-build-time default without a
+// rebuild, e.g. for testing against a variants file that hasn't shipped yet.
 pub fn get_variants() -> &'static Value {{
     VARIANTS_CACHE.get_or_init(|| {{
+        if let Ok(path) = std::env::var("KERNEL_BUILDER_VARIANTS_PATH") {{
+            match std::fs::read_to_string(&path).map(|contents| serde_json::from_str(&contents)) {{
+                Ok(Ok(value)) => return value,
+                Ok(Err(e)) => eprintln!(
+                    "warning: failed to parse KERNEL_BUILDER_VARIANTS_PATH={{path}}: {{e}}, falling back to built-in variants"
+                ),
+                Err(e) => eprintln!(
+                    "warning: failed to read KERNEL_BUILDER_VARIANTS_PATH={{path}}: {{e}}, falling back to built-in variants"
+                ),
+            }}
+        }}
+
         serde_json::from_str(VARIANTS_DATA).unwrap_or_else(|_| {{
             // Provide a fallback empty object if parsing fails
             serde_json::json!({{}})